@@ -1,5 +1,6 @@
 mod models;
 mod parser;
+mod snowflake;
 
 uniffi::setup_scaffolding!();
 use lazy_static::lazy_static;
@@ -11,7 +12,7 @@ use std::thread;
 use uuid::Uuid;
 use zip::ZipArchive;
 
-use crate::models::ExtractObserver;
+use crate::models::{ExtractObserver, ExtractionOptions};
 use crate::parser::{Callback, Parser, Step};
 
 lazy_static! {
@@ -26,11 +27,12 @@ fn cleanup_extraction(extraction_id: &str) {
     }
 }
 
-#[uniffi::export]
+#[uniffi::export(default(options = None))]
 fn start_extraction(
     path: String,
     process_analytics: bool,
     observer: Arc<dyn ExtractObserver>,
+    options: Option<ExtractionOptions>,
 ) -> Option<String> {
     let callback = Callback::new(observer);
     let extraction_id = Uuid::new_v4().to_string();
@@ -85,7 +87,7 @@ fn start_extraction(
             }
         };
 
-        let mut parser = Parser::new(&cancellation_token);
+        let mut parser = Parser::new(&cancellation_token, options.unwrap_or_default());
 
         match parser.process_data(&mut archive, &callback) {
             Ok(data) => {
@@ -189,7 +191,7 @@ mod tests {
         let (sender, receiver) = mpsc::channel();
         let observer = Arc::new(TestObserver::new("TestRun", sender));
         println!("Starting extraction");
-        let _ = start_extraction(file_path.clone(), true, observer);
+        let _ = start_extraction(file_path.clone(), true, observer, None);
 
         match receiver.recv_timeout(std::time::Duration::from_secs(300)) {
             Ok(_) => println!("Test completed successfully"),
@@ -0,0 +1,18 @@
+#[derive(Debug, Clone, Default, uniffi::Record)]
+pub struct ActivityStats {
+    /// Most played first.
+    pub applications: Vec<ApplicationActivity>,
+    pub application_count: u32,
+    pub total_playtime_seconds: u64,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ApplicationActivity {
+    pub application_id: String,
+    /// Lifetime total reported in `user.json`.
+    pub total_playtime_seconds: u64,
+    /// `user.json` only records the last time an application was played, so this is `None`
+    /// rather than a guess.
+    pub first_played: Option<String>,
+    pub last_played: Option<String>,
+}
@@ -0,0 +1,46 @@
+use super::channel::ChannelCount;
+use super::message::MonthCount;
+
+#[derive(Debug, Clone, Default, uniffi::Record)]
+pub struct AttachmentStats {
+    pub total_count: u32,
+    pub by_extension: Vec<AttachmentTypeCount>,
+    pub by_kind: Vec<AttachmentKindCount>,
+    pub by_channel: Vec<ChannelCount>,
+    pub by_month: Vec<MonthCount>,
+    pub attachments: Vec<Attachment>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, uniffi::Enum)]
+pub enum AttachmentKind {
+    Image,
+    Video,
+    Audio,
+    Document,
+    Archive,
+    Other,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct Attachment {
+    pub url: String,
+    pub file_name: String,
+    pub extension: Option<String>,
+    pub kind: AttachmentKind,
+    pub message_id: String,
+    pub channel_id: String,
+    pub timestamp: Option<String>,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct AttachmentTypeCount {
+    pub extension: String,
+    pub kind: AttachmentKind,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct AttachmentKindCount {
+    pub kind: AttachmentKind,
+    pub count: u32,
+}
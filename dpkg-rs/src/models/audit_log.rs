@@ -0,0 +1,143 @@
+/// Audit log of one guild, from `Servers/<id>/audit-log.json`. Only present for guilds the
+/// user could view the audit log of.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct GuildAuditLog {
+    pub guild_id: String,
+    pub guild_name: String,
+    /// Oldest first.
+    pub entries: Vec<AuditLogEntry>,
+    pub summary: ModerationSummary,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct AuditLogEntry {
+    pub id: String,
+    pub action: AuditLogAction,
+    /// Raw `action_type`, for actions `AuditLogAction` doesn't name.
+    pub action_type: i32,
+    /// Who performed the action.
+    pub user_id: Option<String>,
+    /// The user, channel, role or other object acted on, depending on `action`.
+    pub target_id: Option<String>,
+    pub reason: Option<String>,
+    pub changes: Vec<AuditLogChange>,
+    /// Decoded from the entry ID snowflake.
+    pub timestamp: Option<String>,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct AuditLogChange {
+    pub key: String,
+    /// JSON-encoded, since values can be strings, numbers, objects or arrays.
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, uniffi::Record)]
+pub struct ModerationSummary {
+    pub entry_count: u32,
+    pub ban_count: u32,
+    pub unban_count: u32,
+    pub kick_count: u32,
+    pub prune_count: u32,
+    pub timeout_count: u32,
+    /// Roles created, edited or deleted, and roles given to or taken from members.
+    pub role_change_count: u32,
+    /// Channels created, edited or deleted, including permission overwrites.
+    pub channel_change_count: u32,
+    pub message_delete_count: u32,
+    pub first_entry: Option<String>,
+    pub last_entry: Option<String>,
+}
+
+/// `action_type` values from Discord's audit log documentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum AuditLogAction {
+    GuildUpdate,
+    ChannelCreate,
+    ChannelUpdate,
+    ChannelDelete,
+    ChannelOverwriteCreate,
+    ChannelOverwriteUpdate,
+    ChannelOverwriteDelete,
+    MemberKick,
+    MemberPrune,
+    MemberBanAdd,
+    MemberBanRemove,
+    MemberUpdate,
+    MemberRoleUpdate,
+    MemberMove,
+    MemberDisconnect,
+    BotAdd,
+    RoleCreate,
+    RoleUpdate,
+    RoleDelete,
+    InviteCreate,
+    InviteUpdate,
+    InviteDelete,
+    WebhookCreate,
+    WebhookUpdate,
+    WebhookDelete,
+    EmojiCreate,
+    EmojiUpdate,
+    EmojiDelete,
+    MessageDelete,
+    MessageBulkDelete,
+    MessagePin,
+    MessageUnpin,
+    IntegrationCreate,
+    IntegrationUpdate,
+    IntegrationDelete,
+    ThreadCreate,
+    ThreadUpdate,
+    ThreadDelete,
+    AutoModerationBlockMessage,
+    Other,
+}
+
+impl AuditLogAction {
+    pub fn from_code(code: i32) -> Self {
+        match code {
+            1 => Self::GuildUpdate,
+            10 => Self::ChannelCreate,
+            11 => Self::ChannelUpdate,
+            12 => Self::ChannelDelete,
+            13 => Self::ChannelOverwriteCreate,
+            14 => Self::ChannelOverwriteUpdate,
+            15 => Self::ChannelOverwriteDelete,
+            20 => Self::MemberKick,
+            21 => Self::MemberPrune,
+            22 => Self::MemberBanAdd,
+            23 => Self::MemberBanRemove,
+            24 => Self::MemberUpdate,
+            25 => Self::MemberRoleUpdate,
+            26 => Self::MemberMove,
+            27 => Self::MemberDisconnect,
+            28 => Self::BotAdd,
+            30 => Self::RoleCreate,
+            31 => Self::RoleUpdate,
+            32 => Self::RoleDelete,
+            40 => Self::InviteCreate,
+            41 => Self::InviteUpdate,
+            42 => Self::InviteDelete,
+            50 => Self::WebhookCreate,
+            51 => Self::WebhookUpdate,
+            52 => Self::WebhookDelete,
+            60 => Self::EmojiCreate,
+            61 => Self::EmojiUpdate,
+            62 => Self::EmojiDelete,
+            72 => Self::MessageDelete,
+            73 => Self::MessageBulkDelete,
+            74 => Self::MessagePin,
+            75 => Self::MessageUnpin,
+            80 => Self::IntegrationCreate,
+            81 => Self::IntegrationUpdate,
+            82 => Self::IntegrationDelete,
+            110 => Self::ThreadCreate,
+            111 => Self::ThreadUpdate,
+            112 => Self::ThreadDelete,
+            143 => Self::AutoModerationBlockMessage,
+            _ => Self::Other,
+        }
+    }
+}
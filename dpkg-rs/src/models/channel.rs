@@ -2,10 +2,18 @@
 pub struct TopChannel {
     pub id: String,
     pub name: Option<String>,
+    /// Type as written in the package, either a number or a name like `GUILD_TEXT`.
     pub r#type: Option<String>,
+    pub kind: ChannelKind,
     pub message_count: u32,
     pub guild_name: Option<String>,
     pub guild_id: Option<String>,
+    /// Decoded from the channel ID snowflake.
+    pub created_at: Option<String>,
+    /// Parent channel of a thread.
+    pub parent_id: Option<String>,
+    /// Threads whose messages were added to this entry by `ExtractionOptions.roll_up_threads`.
+    pub thread_count: u32,
 }
 
 #[derive(Debug, Clone, uniffi::Record)]
@@ -13,4 +21,157 @@ pub struct TopDM {
     pub id: String,
     pub dm_user_id: String,
     pub message_count: u32,
+    /// Decoded from the channel ID snowflake.
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ChannelCount {
+    pub channel_id: String,
+    pub channel_name: Option<String>,
+    pub count: u32,
+}
+
+/// Normalized channel type. Packages write `DChannel.type` either as Discord's numeric type or
+/// as its name, depending on their age.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, uniffi::Enum)]
+pub enum ChannelKind {
+    Text,
+    /// Text chat of a voice channel.
+    Voice,
+    Category,
+    Announcement,
+    /// Public, private and announcement threads.
+    Thread,
+    /// Thread whose parent is a forum or media channel. `from_type` can't tell these apart
+    /// from other threads, so the parser assigns it once it knows the parent's kind.
+    ForumPost,
+    Stage,
+    Directory,
+    /// Forum and media channels.
+    Forum,
+    Dm,
+    GroupDm,
+    Unknown,
+}
+
+impl ChannelKind {
+    /// Falls back to the number of recipients when the type is missing.
+    pub fn from_type(r#type: Option<&str>, recipient_count: Option<usize>) -> Self {
+        let kind = r#type.map_or(Self::Unknown, |t| {
+            let t = t.trim().to_ascii_uppercase();
+            match t.strip_prefix("GUILD_").unwrap_or(&t) {
+                "0" | "TEXT" => Self::Text,
+                "1" | "DM" => Self::Dm,
+                "2" | "VOICE" => Self::Voice,
+                "3" | "GROUP_DM" => Self::GroupDm,
+                "4" | "CATEGORY" => Self::Category,
+                "5" | "NEWS" | "ANNOUNCEMENT" => Self::Announcement,
+                "10"
+                | "11"
+                | "12"
+                | "NEWS_THREAD"
+                | "ANNOUNCEMENT_THREAD"
+                | "PUBLIC_THREAD"
+                | "PRIVATE_THREAD" => Self::Thread,
+                "13" | "STAGE_VOICE" => Self::Stage,
+                "14" | "DIRECTORY" => Self::Directory,
+                "15" | "16" | "FORUM" | "MEDIA" => Self::Forum,
+                _ => Self::Unknown,
+            }
+        });
+        match (kind, recipient_count) {
+            (Self::Unknown, Some(2)) => Self::Dm,
+            (Self::Unknown, Some(n)) if n > 2 => Self::GroupDm,
+            _ => kind,
+        }
+    }
+
+    pub fn is_thread(self) -> bool {
+        matches!(self, Self::Thread | Self::ForumPost)
+    }
+
+    pub fn is_private(self) -> bool {
+        matches!(self, Self::Dm | Self::GroupDm)
+    }
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ChannelKindCount {
+    pub kind: ChannelKind,
+    pub channel_count: u32,
+    pub message_count: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_numeric_types() {
+        assert_eq!(ChannelKind::from_type(Some("0"), None), ChannelKind::Text);
+        assert_eq!(ChannelKind::from_type(Some("1"), None), ChannelKind::Dm);
+        assert_eq!(
+            ChannelKind::from_type(Some("3"), None),
+            ChannelKind::GroupDm
+        );
+        assert_eq!(
+            ChannelKind::from_type(Some("5"), None),
+            ChannelKind::Announcement
+        );
+        assert_eq!(
+            ChannelKind::from_type(Some("11"), None),
+            ChannelKind::Thread
+        );
+        assert_eq!(ChannelKind::from_type(Some("15"), None), ChannelKind::Forum);
+        assert_eq!(ChannelKind::from_type(Some("16"), None), ChannelKind::Forum);
+    }
+
+    #[test]
+    fn maps_type_names_with_or_without_the_guild_prefix() {
+        assert_eq!(
+            ChannelKind::from_type(Some("GUILD_TEXT"), None),
+            ChannelKind::Text
+        );
+        assert_eq!(
+            ChannelKind::from_type(Some("text"), None),
+            ChannelKind::Text
+        );
+        assert_eq!(
+            ChannelKind::from_type(Some(" guild_news "), None),
+            ChannelKind::Announcement
+        );
+        assert_eq!(
+            ChannelKind::from_type(Some("GUILD_PUBLIC_THREAD"), None),
+            ChannelKind::Thread
+        );
+        assert_eq!(
+            ChannelKind::from_type(Some("GUILD_MEDIA"), None),
+            ChannelKind::Forum
+        );
+        assert_eq!(
+            ChannelKind::from_type(Some("GROUP_DM"), None),
+            ChannelKind::GroupDm
+        );
+    }
+
+    #[test]
+    fn falls_back_to_the_recipient_count() {
+        assert_eq!(ChannelKind::from_type(None, Some(2)), ChannelKind::Dm);
+        assert_eq!(ChannelKind::from_type(None, Some(5)), ChannelKind::GroupDm);
+        assert_eq!(
+            ChannelKind::from_type(Some("99"), Some(3)),
+            ChannelKind::GroupDm
+        );
+        assert_eq!(ChannelKind::from_type(None, Some(1)), ChannelKind::Unknown);
+        assert_eq!(ChannelKind::from_type(None, None), ChannelKind::Unknown);
+    }
+
+    #[test]
+    fn prefers_a_known_type_over_the_recipient_count() {
+        assert_eq!(
+            ChannelKind::from_type(Some("0"), Some(2)),
+            ChannelKind::Text
+        );
+    }
 }
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Deserializer};
 
+use crate::models::ChannelKind;
+use crate::models::user::lenient_vec;
+
 #[derive(Debug, Deserialize)]
 pub struct DChannel {
     pub id: String,
@@ -8,26 +11,89 @@ pub struct DChannel {
     pub r#type: Option<String>,
     pub recipients: Option<Vec<String>>,
     pub guild: Option<DGuild>,
+    /// Channel a thread or forum post was created in, when the package records it.
+    #[serde(default, alias = "parent", deserialize_with = "deserialize_type_field")]
+    pub parent_id: Option<String>,
+}
+
+impl DChannel {
+    pub fn kind(&self) -> ChannelKind {
+        ChannelKind::from_type(
+            self.r#type.as_deref(),
+            self.recipients.as_ref().map(Vec::len),
+        )
+    }
 }
 
-#[derive(Debug, Deserialize, Clone, uniffi::Record)]
+/// A guild from `Servers/index.json`, filled in from `Servers/<id>/guild.json` when present.
+/// Channels only carry the ID and name.
+#[derive(Debug, Default, Deserialize, Clone, uniffi::Record)]
 pub struct DGuild {
     pub id: String,
     pub name: String,
+    /// Decoded from the guild ID snowflake; never read from the package.
+    #[serde(skip)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub owner_id: Option<String>,
+    /// When the user joined, as reported by the package.
+    #[serde(default)]
+    pub joined_at: Option<String>,
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// Whether `owner_id` is the package's user; never read from the package.
+    #[serde(skip)]
+    pub is_owner: bool,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct DMessage {
     #[serde(rename = "ID")]
     pub id: u64,
-    #[serde(rename = "Timestamp")]
-    pub timestamp: String,
+    #[serde(rename = "Timestamp", default)]
+    pub timestamp: Option<String>,
     #[serde(rename = "Contents")]
     pub contents: String,
     #[serde(rename = "Attachments")]
     pub attachments: String,
 }
 
+/// `Servers/<id>/audit-log.json` is usually a bare list of entries, but some packages wrap
+/// it like the API response.
+#[derive(Debug, Deserialize)]
+pub struct DAuditLog {
+    #[serde(default, deserialize_with = "lenient_vec")]
+    pub audit_log_entries: Vec<DAuditLogEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DAuditLogEntry {
+    #[serde(deserialize_with = "deserialize_id_field")]
+    pub id: String,
+    pub action_type: i32,
+    #[serde(default, deserialize_with = "deserialize_type_field")]
+    pub user_id: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_type_field")]
+    pub target_id: Option<String>,
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default, deserialize_with = "lenient_vec")]
+    pub changes: Vec<DAuditLogChange>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DAuditLogChange {
+    pub key: String,
+    #[serde(default)]
+    pub old_value: Option<serde_json::Value>,
+    #[serde(default)]
+    pub new_value: Option<serde_json::Value>,
+}
+
 #[derive(Debug, Deserialize, Default)]
 pub struct DApplicationCommandUsed {
     pub application_id: String,
@@ -49,3 +115,59 @@ where
         _ => Ok(None),
     }
 }
+
+/// Like `deserialize_type_field`, but for IDs an entry can't do without.
+fn deserialize_id_field<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_type_field(deserializer)?
+        .ok_or_else(|| serde::de::Error::custom("expected a string or numeric ID"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignores_derived_guild_fields_in_the_package() {
+        let guild: DGuild = serde_json::from_str(
+            r#"{"id": "1", "name": "Guild", "is_owner": true, "created_at": "2020-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+        assert!(!guild.is_owner);
+        assert_eq!(guild.created_at, None);
+    }
+
+    #[test]
+    fn skips_audit_log_entries_that_do_not_parse() {
+        let log: DAuditLog = serde_json::from_str(
+            r#"{"audit_log_entries": [
+                {"id": "10", "action_type": 22, "user_id": 5},
+                {"id": "11", "action_type": "ban"},
+                {"action_type": 20},
+                {"id": 12, "action_type": 20, "changes": [{"old_value": 1}]}
+            ]}"#,
+        )
+        .unwrap();
+        let ids: Vec<&str> = log
+            .audit_log_entries
+            .iter()
+            .map(|e| e.id.as_str())
+            .collect();
+        assert_eq!(ids, ["10", "12"]);
+        assert_eq!(log.audit_log_entries[0].user_id.as_deref(), Some("5"));
+        assert!(log.audit_log_entries[1].changes.is_empty());
+    }
+
+    #[test]
+    fn accepts_numeric_parent_ids() {
+        let channel: DChannel =
+            serde_json::from_str(r#"{"id": "10", "type": 11, "parent": 2}"#).unwrap();
+        assert_eq!(channel.parent_id.as_deref(), Some("2"));
+
+        let channel: DChannel =
+            serde_json::from_str(r#"{"id": "10", "type": 11, "parent_id": {"id": "2"}}"#).unwrap();
+        assert_eq!(channel.parent_id, None);
+    }
+}
@@ -0,0 +1,20 @@
+use super::message::WordCount;
+
+#[derive(Debug, Clone, Default, uniffi::Record)]
+pub struct EmojiStats {
+    pub custom_count: u32,
+    pub animated_count: u32,
+    pub static_count: u32,
+    pub unicode_count: u32,
+    pub top_custom: Vec<CustomEmoteCount>,
+    /// Unicode emoji counted per grapheme cluster, so ZWJ sequences and skin tones stay whole.
+    pub top_unicode: Vec<WordCount>,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct CustomEmoteCount {
+    pub id: String,
+    pub name: String,
+    pub animated: bool,
+    pub count: u32,
+}
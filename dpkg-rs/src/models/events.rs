@@ -40,3 +40,8 @@ pub struct MostUsedCommand {
     pub command_description: Option<String>,
     pub count: u32,
 }
+
+#[derive(Deserialize)]
+pub struct ClientTimeZoneEvent {
+    pub client_timezone: Option<serde_json::Value>,
+}
@@ -0,0 +1,26 @@
+use crate::models::{MonthCount, RelationshipKind};
+
+#[derive(Debug, Clone, Default, uniffi::Record)]
+pub struct FriendStats {
+    /// Friends with at least one DM message, by message count.
+    pub friends: Vec<DmContact>,
+    pub never_messaged: Vec<DmContact>,
+    /// DMs with anyone who isn't a friend, including blocked users and pending requests.
+    pub non_friends: Vec<DmContact>,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct DmContact {
+    pub user_id: String,
+    /// Known when the user appears in `DUser.relationships`.
+    pub username: Option<String>,
+    pub global_name: Option<String>,
+    pub nickname: Option<String>,
+    pub relationship: Option<RelationshipKind>,
+    pub since: Option<String>,
+    pub dm_channel_id: Option<String>,
+    pub message_count: u32,
+    pub first_dm: Option<String>,
+    pub last_dm: Option<String>,
+    pub busiest_month: Option<MonthCount>,
+}
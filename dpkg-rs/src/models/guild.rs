@@ -0,0 +1,15 @@
+/// Message statistics of one guild, summed over all of its channels.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct TopGuild {
+    pub id: String,
+    pub name: String,
+    pub message_count: u32,
+    pub character_count: u32,
+    /// Channels with at least one message.
+    pub active_channel_count: u32,
+    pub first_message: Option<String>,
+    pub last_message: Option<String>,
+    pub hours_values: Vec<u32>,
+    /// Not in `Servers/`, so the user left the guild or it was deleted.
+    pub left: bool,
+}
@@ -0,0 +1,31 @@
+#[derive(Debug, Clone, Default, uniffi::Record)]
+pub struct LanguageStats {
+    /// Messages long enough for a reliable detection.
+    pub detected_count: u32,
+    pub by_language: Vec<LanguageCount>,
+    pub by_channel: Vec<ChannelLanguages>,
+    pub by_year: Vec<YearLanguages>,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct LanguageCount {
+    /// ISO 639-3 code, e.g. `eng`.
+    pub code: String,
+    pub name: String,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ChannelLanguages {
+    pub channel_id: String,
+    pub channel_name: Option<String>,
+    pub detected_count: u32,
+    pub languages: Vec<LanguageCount>,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct YearLanguages {
+    pub year: i32,
+    pub detected_count: u32,
+    pub languages: Vec<LanguageCount>,
+}
@@ -0,0 +1,14 @@
+use super::channel::ChannelCount;
+use super::message::WordCount;
+
+#[derive(Debug, Clone, Default, uniffi::Record)]
+pub struct LinkStats {
+    /// Every link sent, invites included.
+    pub total_count: u32,
+    pub invite_count: u32,
+    /// Domains and links below leave out invites, which are listed in `top_invites`.
+    pub top_domains: Vec<WordCount>,
+    pub top_links: Vec<WordCount>,
+    pub top_invites: Vec<WordCount>,
+    pub by_channel: Vec<ChannelCount>,
+}
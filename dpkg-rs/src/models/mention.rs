@@ -0,0 +1,17 @@
+#[derive(Debug, Clone, Default, uniffi::Record)]
+pub struct MentionStats {
+    pub total_count: u32,
+    pub everyone_count: u32,
+    pub here_count: u32,
+    pub top_users: Vec<MentionCount>,
+    pub top_roles: Vec<MentionCount>,
+    pub top_channels: Vec<MentionCount>,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct MentionCount {
+    pub id: String,
+    /// Resolved from relationships (users) or the package's channels, when known.
+    pub name: Option<String>,
+    pub count: u32,
+}
@@ -1,12 +1,19 @@
+use chrono::{DateTime, Utc};
+
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct Message {
     pub id: u64,
-    pub timestamp: String,
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Set when `Timestamp` was missing or unparsable and the ID snowflake was used instead.
+    pub timestamp_from_id: bool,
     pub content: String,
     pub attachments: Vec<String>,
+    pub links: Vec<String>,
     pub length: u32,
     pub words: Vec<String>,
+    /// Indices into `words` where dropped tokens were removed, so phrases don't span them.
+    pub phrase_breaks: Vec<usize>,
 }
 
 #[derive(Debug, Clone, uniffi::Record)]
@@ -14,3 +21,10 @@ pub struct WordCount {
     pub word: String,
     pub count: u32,
 }
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct MonthCount {
+    /// Month in `YYYY-MM` form, in the extraction time zone.
+    pub month: String,
+    pub count: u32,
+}
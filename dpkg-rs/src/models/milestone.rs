@@ -0,0 +1,39 @@
+#[derive(Debug, Clone, Default, uniffi::Record)]
+pub struct Milestones {
+    /// Decoded from the `DUser.id` snowflake.
+    pub account_created_at: Option<String>,
+    pub account_age_days: Option<u32>,
+    pub first_message: Option<MessageMilestone>,
+    pub first_dm: Option<MessageMilestone>,
+    pub last_message: Option<MessageMilestone>,
+    /// First message sent in each of `UserData.top_guilds`, in ranking order.
+    pub first_guild_messages: Vec<MessageMilestone>,
+    /// Friend with the earliest `since` date.
+    pub oldest_relationship: Option<RelationshipMilestone>,
+    /// When the 1,000th, 10,000th and 100,000th messages were sent, as far as reached.
+    pub message_count_milestones: Vec<CountMilestone>,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct MessageMilestone {
+    pub message_id: String,
+    pub channel_id: String,
+    pub channel_name: Option<String>,
+    pub guild_id: Option<String>,
+    pub guild_name: Option<String>,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct RelationshipMilestone {
+    pub user_id: String,
+    pub username: String,
+    pub global_name: Option<String>,
+    pub since: String,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct CountMilestone {
+    pub count: u32,
+    pub timestamp: String,
+}
@@ -1,15 +1,45 @@
+pub mod activity;
+pub mod attachment;
+pub mod audit_log;
 pub mod channel;
 pub mod discord_models;
+pub mod emoji;
 pub mod events;
+pub mod friend;
+pub mod guild;
+pub mod language;
+pub mod link;
+pub mod mention;
 pub mod message;
+pub mod milestone;
 pub mod observer;
+pub mod options;
+pub mod payment;
+pub mod phrase;
+pub mod relationship;
+pub mod style;
 pub mod user;
 pub mod user_data;
 
+pub use activity::*;
+pub use attachment::*;
+pub use audit_log::*;
 pub use channel::*;
 pub use discord_models::*;
+pub use emoji::*;
 pub use events::*;
+pub use friend::*;
+pub use guild::*;
+pub use language::*;
+pub use link::*;
+pub use mention::*;
 pub use message::*;
+pub use milestone::*;
 pub use observer::*;
+pub use options::*;
+pub use payment::*;
+pub use phrase::*;
+pub use relationship::*;
+pub use style::*;
 pub use user::*;
 pub use user_data::*;
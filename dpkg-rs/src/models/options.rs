@@ -0,0 +1,99 @@
+/// Minimum length, in characters, of a non-CJK word counted in `favorite_words`.
+pub const DEFAULT_MIN_WORD_LENGTH: u32 = 3;
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ExtractionOptions {
+    /// Time zone used for every hour/day/month statistic. Defaults to UTC.
+    #[uniffi(default = None)]
+    pub time_zone: Option<TimeZoneSetting>,
+    /// ISO 639-1 codes of the bundled stop-word lists to apply. `None` applies only English,
+    /// since the other lists contain common English words such as "man" or "come".
+    #[uniffi(default = None)]
+    pub stop_word_languages: Option<Vec<String>>,
+    /// Extra words ignored on top of the bundled lists.
+    #[uniffi(default = [])]
+    pub custom_stop_words: Vec<String>,
+    #[uniffi(default = 3)]
+    pub min_word_length: u32,
+    /// Also compute the most common 2- and 3-word phrases, which costs extra memory.
+    #[uniffi(default = false)]
+    pub compute_phrases: bool,
+    /// Detect each message's language with the bundled offline model. Slow on big packages.
+    #[uniffi(default = false)]
+    pub detect_languages: bool,
+    /// Size of `top_channels`. Like every limit below, `None` returns the complete ranking.
+    #[uniffi(default = Some(10))]
+    pub top_channels_limit: Option<u32>,
+    #[uniffi(default = Some(10))]
+    pub top_dms_limit: Option<u32>,
+    #[uniffi(default = Some(10))]
+    pub top_guilds_limit: Option<u32>,
+    /// Applies to words and phrases.
+    #[uniffi(default = Some(10))]
+    pub top_words_limit: Option<u32>,
+    /// Applies to custom emotes and Unicode emoji.
+    #[uniffi(default = Some(10))]
+    pub top_emotes_limit: Option<u32>,
+    #[uniffi(default = Some(20))]
+    pub top_commands_limit: Option<u32>,
+    /// Size of `activities.applications`, most played first.
+    #[uniffi(default = Some(10))]
+    pub top_applications_limit: Option<u32>,
+    /// Count thread messages towards their parent channel in `top_channels` instead of
+    /// ranking each thread on its own; per-channel phrases follow. Parents missing from the
+    /// package get an entry without a name.
+    #[uniffi(default = false)]
+    pub roll_up_threads: bool,
+    /// Applies to every other ranking: links, domains, invites and mentions.
+    #[uniffi(default = Some(10))]
+    pub top_list_limit: Option<u32>,
+    /// ISO 4217 code payment totals are converted into, e.g. `EUR`. `None` skips conversion.
+    #[uniffi(default = None)]
+    pub target_currency: Option<String>,
+    /// Overrides or extends the bundled exchange rate table.
+    #[uniffi(default = [])]
+    pub exchange_rates: Vec<ExchangeRate>,
+}
+
+impl Default for ExtractionOptions {
+    fn default() -> Self {
+        Self {
+            time_zone: None,
+            stop_word_languages: None,
+            custom_stop_words: Vec::new(),
+            min_word_length: DEFAULT_MIN_WORD_LENGTH,
+            compute_phrases: false,
+            detect_languages: false,
+            top_channels_limit: Some(10),
+            top_dms_limit: Some(10),
+            top_guilds_limit: Some(10),
+            top_words_limit: Some(10),
+            top_emotes_limit: Some(10),
+            top_commands_limit: Some(20),
+            top_applications_limit: Some(10),
+            roll_up_threads: false,
+            top_list_limit: Some(10),
+            target_currency: None,
+            exchange_rates: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ExchangeRate {
+    /// ISO 4217 code.
+    pub currency: String,
+    /// Units of `currency` per US dollar.
+    pub units_per_usd: f64,
+}
+
+#[derive(Debug, Clone, uniffi::Enum)]
+pub enum TimeZoneSetting {
+    /// IANA time zone name, e.g. `America/Los_Angeles`.
+    Iana { name: String },
+    /// Fixed offset from UTC in minutes, e.g. `-480` for UTC-8.
+    FixedOffset { offset_minutes: i32 },
+    /// Use the `client_timezone` most often reported in the analytics events. Numeric values
+    /// are read like JavaScript's `getTimezoneOffset()`, i.e. minutes west of UTC.
+    InferFromAnalytics,
+}
@@ -0,0 +1,126 @@
+#[derive(Debug, Clone, Default, uniffi::Record)]
+pub struct PaymentStats {
+    /// Completed payments only. Amounts are in the currency's minor units, e.g. cents.
+    pub totals: Vec<CurrencyAmount>,
+    pub by_year: Vec<YearSpending>,
+    pub by_product: Vec<ProductSpending>,
+    /// Every payment grouped by status, including failed and refunded ones.
+    pub by_status: Vec<StatusSummary>,
+    /// Paid Nitro subscription periods, oldest first. Gifts are not included.
+    pub nitro_timeline: Vec<NitroPeriod>,
+    pub nitro_gaps: Vec<NitroGap>,
+    /// All payments, oldest first. Payments whose `created_at` couldn't be parsed come last.
+    pub payments: Vec<Payment>,
+    /// `ExtractionOptions.target_currency`, when set and a rate for it is known. Every
+    /// `converted_*` field below is in this currency's minor units.
+    pub converted_currency: Option<String>,
+    /// Completed payments converted and summed. Currencies without a known rate are
+    /// left out and listed in `unconverted_currencies`.
+    pub converted_total: Option<i64>,
+    pub unconverted_currencies: Vec<String>,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct CurrencyAmount {
+    /// Uppercase ISO 4217 code.
+    pub currency: String,
+    pub amount: i64,
+    /// Decimals in the currency's minor unit, e.g. 2 for USD and 0 for JPY.
+    pub minor_units: u32,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct YearSpending {
+    pub year: i32,
+    pub totals: Vec<CurrencyAmount>,
+    pub converted_total: Option<i64>,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ProductSpending {
+    pub description: String,
+    pub kind: ProductKind,
+    pub totals: Vec<CurrencyAmount>,
+    pub converted_total: Option<i64>,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct StatusSummary {
+    pub status: PaymentStatus,
+    pub totals: Vec<CurrencyAmount>,
+    pub converted_total: Option<i64>,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct Payment {
+    pub description: String,
+    pub kind: ProductKind,
+    pub status: PaymentStatus,
+    pub currency: String,
+    pub amount: i64,
+    pub minor_units: u32,
+    /// `None` when there's no target currency or no rate for `currency`.
+    pub converted_amount: Option<i64>,
+    /// `None` when `created_at` couldn't be parsed.
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct NitroPeriod {
+    pub kind: ProductKind,
+    pub start: String,
+    /// When the last payment of the period ran out.
+    pub end: String,
+    pub payment_count: u32,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct NitroGap {
+    pub start: String,
+    pub end: String,
+    pub days: u32,
+}
+
+/// `DPayment.status`, numbered 0 to 5.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, uniffi::Enum)]
+pub enum PaymentStatus {
+    Pending,
+    Completed,
+    Failed,
+    Reversed,
+    Refunded,
+    Canceled,
+    Unknown,
+}
+
+impl PaymentStatus {
+    pub fn from_code(code: i32) -> Self {
+        match code {
+            0 => Self::Pending,
+            1 => Self::Completed,
+            2 => Self::Failed,
+            3 => Self::Reversed,
+            4 => Self::Refunded,
+            5 => Self::Canceled,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Guessed from `DPayment.description`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum ProductKind {
+    Nitro,
+    NitroBasic,
+    NitroClassic,
+    ServerBoost,
+    Gift,
+    Other,
+}
+
+impl ProductKind {
+    pub fn is_nitro_subscription(self) -> bool {
+        matches!(self, Self::Nitro | Self::NitroBasic | Self::NitroClassic)
+    }
+}
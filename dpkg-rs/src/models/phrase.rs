@@ -0,0 +1,17 @@
+use super::message::WordCount;
+
+#[derive(Debug, Clone, Default, uniffi::Record)]
+pub struct PhraseStats {
+    pub top_bigrams: Vec<WordCount>,
+    pub top_trigrams: Vec<WordCount>,
+    /// Phrases for each entry of `top_channels`, in the same order.
+    pub by_channel: Vec<ChannelPhrases>,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ChannelPhrases {
+    pub channel_id: String,
+    pub channel_name: Option<String>,
+    pub top_bigrams: Vec<WordCount>,
+    pub top_trigrams: Vec<WordCount>,
+}
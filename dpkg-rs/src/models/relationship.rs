@@ -0,0 +1,39 @@
+use serde::Serialize;
+
+/// `DRelationship.type`, numbered 1 to 5 in `user.json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, uniffi::Enum)]
+pub enum RelationshipKind {
+    Friend,
+    Blocked,
+    IncomingRequest,
+    OutgoingRequest,
+    /// Suggested from mutual activity without an explicit friend request.
+    Implicit,
+    /// Missing or a type this version doesn't know about.
+    Unknown,
+}
+
+impl RelationshipKind {
+    pub fn from_code(code: i64) -> Self {
+        match code {
+            1 => Self::Friend,
+            2 => Self::Blocked,
+            3 => Self::IncomingRequest,
+            4 => Self::OutgoingRequest,
+            5 => Self::Implicit,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, uniffi::Record)]
+pub struct RelationshipSummary {
+    pub friend_count: u32,
+    pub blocked_count: u32,
+    pub incoming_request_count: u32,
+    pub outgoing_request_count: u32,
+    pub implicit_count: u32,
+    pub unknown_count: u32,
+    /// Relationships with a nickname set, of any kind.
+    pub nicknamed_count: u32,
+}
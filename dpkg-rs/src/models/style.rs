@@ -0,0 +1,33 @@
+#[derive(Debug, Clone, Default, uniffi::Record)]
+pub struct MessageStyleStats {
+    /// Lengths are in Unicode scalar values and only cover messages with text.
+    pub length_histogram: Vec<LengthBucket>,
+    pub average_length: f64,
+    /// Lower of the two middle lengths when the message count is even.
+    pub median_length: u32,
+    pub longest_message: Option<LongestMessage>,
+    pub links_only_count: u32,
+    pub emoji_only_count: u32,
+    pub attachments_only_count: u32,
+    pub code_block_count: u32,
+    pub inline_code_count: u32,
+    pub spoiler_count: u32,
+    pub quote_count: u32,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct LengthBucket {
+    pub min: u32,
+    /// Inclusive upper bound, `None` for the last, open-ended bucket.
+    pub max: Option<u32>,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct LongestMessage {
+    pub message_id: String,
+    pub channel_id: String,
+    pub channel_name: Option<String>,
+    pub length: u32,
+    pub timestamp: Option<String>,
+}
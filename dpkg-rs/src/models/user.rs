@@ -1,5 +1,13 @@
-use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
 
+use crate::models::RelationshipKind;
+
+/// `Account/user.json`. Every section past the basic profile is optional, and entries that
+/// don't match the model are skipped rather than failing the whole file, since the
+/// layout differs between package versions.
 #[derive(Debug, Clone, Serialize, Deserialize, uniffi::Record)]
 pub struct DUser {
     pub id: String,
@@ -7,13 +15,53 @@ pub struct DUser {
     pub global_name: Option<String>,
     pub discriminator: u16,
     pub avatar_hash: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub verified: Option<bool>,
+    #[serde(default)]
+    pub phone: Option<String>,
+    #[serde(default)]
+    pub has_mobile: Option<bool>,
+    #[serde(default)]
+    pub premium_until: Option<String>,
+    /// Raw user flags bitfield.
+    #[serde(default)]
+    pub flags: Option<u64>,
+    #[serde(default, deserialize_with = "lenient_vec")]
     pub payments: Vec<DPayment>,
+    #[serde(default, deserialize_with = "lenient_vec")]
     pub relationships: Vec<DRelationship>,
+    #[serde(default, deserialize_with = "lenient_vec")]
+    pub connections: Vec<DConnection>,
+    #[serde(default, deserialize_with = "lenient_option")]
+    pub settings: Option<DUserSettings>,
+    /// Notes keyed by the ID of the user they were written about.
+    #[serde(default, deserialize_with = "deserialize_notes")]
+    pub notes: HashMap<String, String>,
+    #[serde(default, deserialize_with = "lenient_vec")]
+    pub guild_settings: Vec<DGuildSettings>,
+    #[serde(default, deserialize_with = "lenient_vec")]
+    pub entitlements: Vec<DEntitlement>,
+    #[serde(default, alias = "user_sessions", deserialize_with = "lenient_vec")]
+    pub sessions: Vec<DSession>,
+    #[serde(default, deserialize_with = "lenient_vec")]
+    pub user_activity_application_statistics: Vec<DApplicationStatistic>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, uniffi::Record)]
 pub struct DRelationship {
+    #[serde(
+        default = "unknown_relationship",
+        deserialize_with = "deserialize_relationship_kind"
+    )]
+    pub r#type: RelationshipKind,
+    #[serde(default)]
+    pub nickname: Option<String>,
     pub user: DRelationshipUser,
+    /// When the relationship started, e.g. when a friend request was accepted.
+    #[serde(default)]
+    pub since: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, uniffi::Record)]
@@ -33,3 +81,191 @@ pub struct DPayment {
     pub created_at: String,
     pub description: String,
 }
+
+/// An account linked under Settings > Connections.
+#[derive(Debug, Clone, Serialize, Deserialize, uniffi::Record)]
+pub struct DConnection {
+    /// Service name, e.g. `spotify` or `steam`.
+    pub r#type: String,
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub revoked: bool,
+    #[serde(default)]
+    pub verified: bool,
+    /// 0 is only visible to the user, 1 to everyone.
+    #[serde(default)]
+    pub visibility: i32,
+    #[serde(default)]
+    pub friend_sync: bool,
+    #[serde(default)]
+    pub show_activity: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, uniffi::Record)]
+#[serde(default)]
+pub struct DUserSettings {
+    pub locale: Option<String>,
+    pub theme: Option<String>,
+    pub status: Option<String>,
+    pub developer_mode: Option<bool>,
+    pub message_display_compact: Option<bool>,
+    pub show_current_game: Option<bool>,
+    pub explicit_content_filter: Option<i32>,
+    pub timezone_offset: Option<i32>,
+    pub default_guilds_restricted: Option<bool>,
+    #[serde(deserialize_with = "lenient_vec")]
+    pub restricted_guilds: Vec<String>,
+    #[serde(deserialize_with = "lenient_vec")]
+    pub guild_positions: Vec<String>,
+}
+
+/// Per-guild notification settings.
+#[derive(Debug, Clone, Serialize, Deserialize, uniffi::Record)]
+pub struct DGuildSettings {
+    /// `None` for the settings that apply to DMs.
+    #[serde(default)]
+    pub guild_id: Option<String>,
+    #[serde(default)]
+    pub muted: bool,
+    #[serde(default)]
+    pub suppress_everyone: bool,
+    #[serde(default)]
+    pub suppress_roles: bool,
+    #[serde(default)]
+    pub mobile_push: bool,
+    /// 0 all messages, 1 only mentions, 2 nothing, 3 the guild default.
+    #[serde(default)]
+    pub message_notifications: Option<i32>,
+    #[serde(default, deserialize_with = "lenient_vec")]
+    pub channel_overrides: Vec<DChannelOverride>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, uniffi::Record)]
+pub struct DChannelOverride {
+    pub channel_id: String,
+    #[serde(default)]
+    pub muted: bool,
+    #[serde(default)]
+    pub message_notifications: Option<i32>,
+}
+
+/// A purchase or gift the account owns, such as Nitro or a game.
+#[derive(Debug, Clone, Serialize, Deserialize, uniffi::Record)]
+pub struct DEntitlement {
+    pub id: String,
+    pub sku_id: String,
+    #[serde(default)]
+    pub application_id: Option<String>,
+    #[serde(default)]
+    pub r#type: Option<i32>,
+    #[serde(default)]
+    pub deleted: bool,
+    #[serde(default)]
+    pub starts_at: Option<String>,
+    #[serde(default)]
+    pub ends_at: Option<String>,
+    #[serde(default, deserialize_with = "lenient_option")]
+    pub sku: Option<DEntitlementSku>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, uniffi::Record)]
+pub struct DEntitlementSku {
+    pub id: String,
+    pub name: String,
+}
+
+/// A device or browser session that was logged into the account.
+#[derive(Debug, Clone, Serialize, Deserialize, uniffi::Record)]
+pub struct DSession {
+    #[serde(default)]
+    pub id_hash: Option<String>,
+    #[serde(default)]
+    pub approx_last_used_time: Option<String>,
+    #[serde(default, deserialize_with = "lenient_option")]
+    pub client_info: Option<DSessionClientInfo>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, uniffi::Record)]
+#[serde(default)]
+pub struct DSessionClientInfo {
+    pub os: Option<String>,
+    pub platform: Option<String>,
+    pub location: Option<String>,
+}
+
+/// Lifetime playtime of one application, as tracked by the client.
+#[derive(Debug, Clone, Serialize, Deserialize, uniffi::Record)]
+pub struct DApplicationStatistic {
+    pub application_id: String,
+    /// Seconds.
+    #[serde(default)]
+    pub total_duration: u64,
+    #[serde(default)]
+    pub last_played_at: Option<String>,
+}
+
+/// Deserializes a list, skipping entries that don't match `T`. Anything but an array
+/// becomes an empty list.
+pub(super) fn lenient_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let value: Option<Value> = Option::deserialize(deserializer)?;
+
+    match value {
+        Some(Value::Array(items)) => Ok(items
+            .into_iter()
+            .filter_map(|item| serde_json::from_value(item).ok())
+            .collect()),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Deserializes an optional section, treating one that doesn't match `T` as missing.
+fn lenient_option<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let value: Option<Value> = Option::deserialize(deserializer)?;
+
+    Ok(value.and_then(|v| serde_json::from_value(v).ok()))
+}
+
+fn unknown_relationship() -> RelationshipKind {
+    RelationshipKind::Unknown
+}
+
+fn deserialize_relationship_kind<'de, D>(deserializer: D) -> Result<RelationshipKind, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<Value> = Option::deserialize(deserializer)?;
+
+    let code = match value {
+        Some(Value::Number(n)) => n.as_i64(),
+        Some(Value::String(s)) => s.parse().ok(),
+        _ => None,
+    };
+    Ok(code.map_or(RelationshipKind::Unknown, RelationshipKind::from_code))
+}
+
+fn deserialize_notes<'de, D>(deserializer: D) -> Result<HashMap<String, String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<Value> = Option::deserialize(deserializer)?;
+
+    match value {
+        Some(Value::Object(notes)) => Ok(notes
+            .into_iter()
+            .filter_map(|(user_id, note)| match note {
+                Value::String(note) => Some((user_id, note)),
+                _ => None,
+            })
+            .collect()),
+        _ => Ok(HashMap::new()),
+    }
+}
@@ -1,6 +1,11 @@
-use crate::models::DGuild;
+use crate::models::{
+    ActivityStats, AttachmentStats, DGuild, EmojiStats, FriendStats, GuildAuditLog, LanguageStats,
+    LinkStats, MentionStats, MessageStyleStats, Milestones, PaymentStats, PhraseStats,
+    RelationshipSummary,
+};
 
-use super::channel::{TopChannel, TopDM};
+use super::channel::{ChannelKindCount, TopChannel, TopDM};
+use super::guild::TopGuild;
 use super::message::WordCount;
 use super::user::DUser;
 
@@ -9,14 +14,37 @@ pub struct UserData {
     pub user: Option<DUser>,
     pub top_dms: Vec<TopDM>,
     pub top_channels: Vec<TopChannel>,
+    /// Channels and messages per channel kind, by message count.
+    pub channel_kinds: Vec<ChannelKindCount>,
+    pub top_guilds: Vec<TopGuild>,
+    /// Every guild with messages that isn't in `Servers/`, most active first.
+    pub left_guilds: Vec<TopGuild>,
+    /// Guild channels whose guild isn't recorded at all, most active first.
+    pub orphaned_channels: Vec<TopChannel>,
     pub guilds: Vec<DGuild>,
+    pub audit_logs: Vec<GuildAuditLog>,
     pub dm_channel_count: u32,
     pub channel_count: u32,
     pub message_count: u32,
+    /// Total length of all messages in Unicode scalar values.
     pub character_count: u32,
     pub hours_values: Vec<u32>,
     pub favorite_words: Vec<WordCount>,
     pub favorite_emotes: Vec<WordCount>,
+    pub attachments: AttachmentStats,
+    pub links: LinkStats,
+    pub mentions: MentionStats,
+    pub emojis: EmojiStats,
+    pub message_style: MessageStyleStats,
+    pub milestones: Milestones,
+    pub relationships: RelationshipSummary,
+    pub friends: FriendStats,
+    pub payments: PaymentStats,
+    pub activities: ActivityStats,
+    /// Only computed when `ExtractionOptions.compute_phrases` is set.
+    pub phrases: Option<PhraseStats>,
+    /// Only computed when `ExtractionOptions.detect_languages` is set.
+    pub languages: Option<LanguageStats>,
 }
 
 impl Default for UserData {
@@ -25,7 +53,12 @@ impl Default for UserData {
             user: None,
             top_dms: Vec::new(),
             top_channels: Vec::new(),
+            channel_kinds: Vec::new(),
+            top_guilds: Vec::new(),
+            left_guilds: Vec::new(),
+            orphaned_channels: Vec::new(),
             guilds: Vec::new(),
+            audit_logs: Vec::new(),
             dm_channel_count: 0,
             channel_count: 0,
             message_count: 0,
@@ -33,6 +66,18 @@ impl Default for UserData {
             hours_values: vec![0; 24],
             favorite_words: Vec::new(),
             favorite_emotes: Vec::new(),
+            attachments: AttachmentStats::default(),
+            links: LinkStats::default(),
+            mentions: MentionStats::default(),
+            emojis: EmojiStats::default(),
+            message_style: MessageStyleStats::default(),
+            milestones: Milestones::default(),
+            relationships: RelationshipSummary::default(),
+            friends: FriendStats::default(),
+            payments: PaymentStats::default(),
+            activities: ActivityStats::default(),
+            phrases: None,
+            languages: None,
         }
     }
 }
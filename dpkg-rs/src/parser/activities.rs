@@ -0,0 +1,139 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+use crate::models::{ActivityStats, ApplicationActivity, DApplicationStatistic};
+use crate::parser::Parser;
+use crate::parser::utils::limit_or_all;
+
+#[derive(Default)]
+struct ApplicationUsage {
+    playtime: u64,
+    last: Option<DateTime<Utc>>,
+}
+
+impl ApplicationUsage {
+    fn last_played_at(&mut self, timestamp: Option<DateTime<Utc>>) {
+        let Some(timestamp) = timestamp else {
+            return;
+        };
+        if self.last.is_none_or(|last| timestamp > last) {
+            self.last = Some(timestamp);
+        }
+    }
+}
+
+impl<'a> Parser<'a> {
+    /// Application usage from `user_activity_application_statistics` in `user.json`. The
+    /// `Activity/` section of the package only holds analytics events, so this is the only
+    /// per-application source.
+    pub(super) fn activity_stats(&self, statistics: &[DApplicationStatistic]) -> ActivityStats {
+        let mut usage: HashMap<&str, ApplicationUsage> = HashMap::new();
+        for statistic in statistics {
+            let application = usage.entry(&statistic.application_id).or_default();
+            application.playtime = application.playtime.max(statistic.total_duration);
+            application.last_played_at(
+                statistic
+                    .last_played_at
+                    .as_deref()
+                    .and_then(Parser::parse_timestamp),
+            );
+        }
+
+        let mut applications: Vec<ApplicationActivity> = usage
+            .into_iter()
+            .map(|(application_id, application)| ApplicationActivity {
+                application_id: application_id.to_string(),
+                total_playtime_seconds: application.playtime,
+                first_played: None,
+                last_played: application
+                    .last
+                    .map(|t| self.time_zone.localize(t).to_rfc3339()),
+            })
+            .collect();
+        applications.sort_by(|a, b| {
+            b.total_playtime_seconds
+                .cmp(&a.total_playtime_seconds)
+                .then_with(|| a.application_id.cmp(&b.application_id))
+        });
+
+        ActivityStats {
+            application_count: applications.len() as u32,
+            total_playtime_seconds: applications.iter().map(|a| a.total_playtime_seconds).sum(),
+            applications: applications
+                .into_iter()
+                .take(limit_or_all(self.options.top_applications_limit))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ExtractionOptions;
+    use crate::parser::timezone::LocalTimeZone;
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+
+    fn statistic(
+        application_id: &str,
+        seconds: u64,
+        last_played_at: &str,
+    ) -> DApplicationStatistic {
+        DApplicationStatistic {
+            application_id: application_id.to_string(),
+            total_duration: seconds,
+            last_played_at: Some(last_played_at.to_string()),
+        }
+    }
+
+    #[test]
+    fn merges_repeated_applications_and_leaves_first_played_unknown() {
+        let cancellation_token = Arc::new(AtomicBool::new(false));
+        let mut parser = Parser::new(&cancellation_token, Default::default());
+        parser.time_zone = LocalTimeZone::Named(chrono_tz::Europe::Paris);
+        let stats = parser.activity_stats(&[
+            statistic("1", 100, "2021-01-05T10:00:00+00:00"),
+            statistic("1", 300, "2021-03-05T10:00:00+00:00"),
+            statistic("2", 50, "not a date"),
+        ]);
+
+        assert_eq!(stats.application_count, 2);
+        assert_eq!(stats.total_playtime_seconds, 350);
+        let first = &stats.applications[0];
+        assert_eq!(first.application_id, "1");
+        assert_eq!(first.total_playtime_seconds, 300);
+        assert_eq!(first.first_played, None);
+        assert_eq!(
+            first.last_played.as_deref(),
+            Some("2021-03-05T11:00:00+01:00")
+        );
+        assert_eq!(stats.applications[1].last_played, None);
+    }
+
+    #[test]
+    fn ranks_by_playtime_then_id_within_the_limit() {
+        let cancellation_token = Arc::new(AtomicBool::new(false));
+        let parser = Parser::new(
+            &cancellation_token,
+            ExtractionOptions {
+                top_applications_limit: Some(2),
+                ..Default::default()
+            },
+        );
+        let stats = parser.activity_stats(&[
+            statistic("3", 10, "2021-01-05T10:00:00+00:00"),
+            statistic("2", 20, "2021-01-05T10:00:00+00:00"),
+            statistic("1", 10, "2021-01-05T10:00:00+00:00"),
+        ]);
+
+        let ids: Vec<_> = stats
+            .applications
+            .iter()
+            .map(|a| a.application_id.as_str())
+            .collect();
+        assert_eq!(ids, ["2", "1"]);
+        assert_eq!(stats.application_count, 3);
+        assert_eq!(stats.total_playtime_seconds, 40);
+    }
+}
@@ -158,9 +158,14 @@ impl<'a> Parser<'a> {
 
         let result = match counts.lock() {
             Ok(mut lock) => {
-                lock.most_used_commands
-                    .sort_by(|a, b| b.count.cmp(&a.count));
-                lock.most_used_commands.truncate(20);
+                lock.most_used_commands.sort_by(|a, b| {
+                    b.count
+                        .cmp(&a.count)
+                        .then_with(|| a.command_id.cmp(&b.command_id))
+                });
+                if let Some(limit) = self.options.top_commands_limit {
+                    lock.most_used_commands.truncate(limit as usize);
+                }
                 lock.clone()
             }
             Err(_) => EventCount::default(),
@@ -0,0 +1,273 @@
+use chrono::{DateTime, FixedOffset};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::models::{
+    Attachment, AttachmentKind, AttachmentKindCount, AttachmentStats, AttachmentTypeCount,
+    ChannelCount, DChannel, Message, MonthCount,
+};
+
+#[derive(Default)]
+pub(super) struct AttachmentCollector {
+    by_extension: HashMap<String, u32>,
+    by_channel: HashMap<String, ChannelCount>,
+    by_month: BTreeMap<String, u32>,
+    attachments: Vec<(i64, Attachment)>,
+}
+
+impl AttachmentCollector {
+    pub(super) fn add(
+        &mut self,
+        channel: &DChannel,
+        message: &Message,
+        local_time: Option<DateTime<FixedOffset>>,
+    ) {
+        if message.attachments.is_empty() {
+            return;
+        }
+
+        let channel_entry = self
+            .by_channel
+            .entry(channel.id.clone())
+            .or_insert_with(|| ChannelCount {
+                channel_id: channel.id.clone(),
+                channel_name: channel.name.clone(),
+                count: 0,
+            });
+        channel_entry.count += message.attachments.len() as u32;
+
+        if let Some(local) = local_time {
+            *self
+                .by_month
+                .entry(local.format("%Y-%m").to_string())
+                .or_insert(0) += message.attachments.len() as u32;
+        }
+
+        for url in &message.attachments {
+            let file_name = attachment_file_name(url);
+            let extension = file_name
+                .rsplit_once('.')
+                .map(|(_, ext)| ext.to_lowercase())
+                .filter(|ext| !ext.is_empty());
+            let kind = extension
+                .as_deref()
+                .map(attachment_kind)
+                .unwrap_or(AttachmentKind::Other);
+
+            *self
+                .by_extension
+                .entry(extension.clone().unwrap_or_default())
+                .or_insert(0) += 1;
+
+            self.attachments.push((
+                local_time.map(|t| t.timestamp()).unwrap_or(i64::MAX),
+                Attachment {
+                    url: url.clone(),
+                    file_name,
+                    extension,
+                    kind,
+                    message_id: message.id.to_string(),
+                    channel_id: channel.id.clone(),
+                    timestamp: local_time.map(|t| t.to_rfc3339()),
+                },
+            ));
+        }
+    }
+
+    pub(super) fn finish(self) -> AttachmentStats {
+        let mut by_extension: Vec<AttachmentTypeCount> = self
+            .by_extension
+            .into_iter()
+            .map(|(extension, count)| AttachmentTypeCount {
+                kind: attachment_kind(&extension),
+                extension,
+                count,
+            })
+            .collect();
+        by_extension.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.extension.cmp(&b.extension))
+        });
+
+        let mut kind_counts: HashMap<AttachmentKind, u32> = HashMap::new();
+        for entry in &by_extension {
+            *kind_counts.entry(entry.kind).or_insert(0) += entry.count;
+        }
+        let mut by_kind: Vec<AttachmentKindCount> = kind_counts
+            .into_iter()
+            .map(|(kind, count)| AttachmentKindCount { kind, count })
+            .collect();
+        by_kind.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| (a.kind as u8).cmp(&(b.kind as u8)))
+        });
+
+        let mut by_channel: Vec<ChannelCount> = self.by_channel.into_values().collect();
+        by_channel.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.channel_id.cmp(&b.channel_id))
+        });
+
+        let mut attachments = self.attachments;
+        attachments.sort_by_key(|(timestamp, _)| *timestamp);
+
+        AttachmentStats {
+            total_count: attachments.len() as u32,
+            by_extension,
+            by_kind,
+            by_channel,
+            by_month: self
+                .by_month
+                .into_iter()
+                .map(|(month, count)| MonthCount { month, count })
+                .collect(),
+            attachments: attachments
+                .into_iter()
+                .map(|(_, attachment)| attachment)
+                .collect(),
+        }
+    }
+}
+
+fn attachment_file_name(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    path.rsplit('/')
+        .find(|segment| !segment.is_empty())
+        .unwrap_or(path)
+        .to_string()
+}
+
+fn attachment_kind(extension: &str) -> AttachmentKind {
+    match extension {
+        "png" | "jpg" | "jpeg" | "gif" | "webp" | "bmp" | "svg" | "heic" | "heif" | "avif"
+        | "tif" | "tiff" => AttachmentKind::Image,
+        "mp4" | "mov" | "webm" | "mkv" | "avi" | "m4v" | "wmv" | "flv" => AttachmentKind::Video,
+        "mp3" | "ogg" | "wav" | "flac" | "m4a" | "aac" | "opus" | "wma" => AttachmentKind::Audio,
+        "pdf" | "txt" | "doc" | "docx" | "xls" | "xlsx" | "ppt" | "pptx" | "odt" | "rtf" | "md"
+        | "csv" | "json" | "log" => AttachmentKind::Document,
+        "zip" | "rar" | "7z" | "tar" | "gz" | "bz2" | "xz" => AttachmentKind::Archive,
+        _ => AttachmentKind::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(id: &str) -> DChannel {
+        DChannel {
+            id: id.to_string(),
+            name: Some(format!("channel-{}", id)),
+            r#type: None,
+            recipients: None,
+            guild: None,
+            parent_id: None,
+        }
+    }
+
+    fn message(id: u64, attachments: &[&str]) -> Message {
+        Message {
+            id,
+            timestamp: None,
+            timestamp_from_id: false,
+            content: String::new(),
+            attachments: attachments.iter().map(|a| a.to_string()).collect(),
+            links: Vec::new(),
+            length: 0,
+            words: Vec::new(),
+            phrase_breaks: Vec::new(),
+        }
+    }
+
+    fn at(raw: &str) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(raw).ok()
+    }
+
+    #[test]
+    fn reads_file_names_and_extensions_from_urls() {
+        assert_eq!(
+            attachment_file_name("https://cdn.discordapp.com/attachments/1/2/Photo.JPG?ex=1#x"),
+            "Photo.JPG"
+        );
+        assert_eq!(attachment_file_name("https://example.com/files/"), "files");
+
+        let mut collector = AttachmentCollector::default();
+        collector.add(
+            &channel("1"),
+            &message(1, &["https://cdn/a/Photo.JPG?ex=1", "https://cdn/a/README"]),
+            None,
+        );
+        let stats = collector.finish();
+        assert_eq!(stats.attachments[0].extension.as_deref(), Some("jpg"));
+        assert_eq!(stats.attachments[0].kind, AttachmentKind::Image);
+        assert_eq!(stats.attachments[1].extension, None);
+        assert_eq!(stats.attachments[1].kind, AttachmentKind::Other);
+    }
+
+    #[test]
+    fn maps_extensions_to_kinds() {
+        assert_eq!(attachment_kind("webp"), AttachmentKind::Image);
+        assert_eq!(attachment_kind("mov"), AttachmentKind::Video);
+        assert_eq!(attachment_kind("opus"), AttachmentKind::Audio);
+        assert_eq!(attachment_kind("pdf"), AttachmentKind::Document);
+        assert_eq!(attachment_kind("7z"), AttachmentKind::Archive);
+        assert_eq!(attachment_kind("exe"), AttachmentKind::Other);
+    }
+
+    #[test]
+    fn counts_by_extension_kind_and_channel() {
+        let mut collector = AttachmentCollector::default();
+        collector.add(
+            &channel("1"),
+            &message(1, &["a.png", "b.png", "c.mp4"]),
+            None,
+        );
+        collector.add(&channel("2"), &message(2, &["d.jpg"]), None);
+        collector.add(&channel("2"), &message(3, &[]), None);
+        let stats = collector.finish();
+
+        assert_eq!(stats.total_count, 4);
+        assert_eq!(stats.by_extension[0].extension, "png");
+        assert_eq!(stats.by_extension[0].count, 2);
+        assert_eq!(stats.by_kind[0].kind, AttachmentKind::Image);
+        assert_eq!(stats.by_kind[0].count, 3);
+        let channels: Vec<(&str, u32)> = stats
+            .by_channel
+            .iter()
+            .map(|c| (c.channel_id.as_str(), c.count))
+            .collect();
+        assert_eq!(channels, [("1", 3), ("2", 1)]);
+    }
+
+    #[test]
+    fn buckets_months_in_local_time_and_sorts_undated_last() {
+        let mut collector = AttachmentCollector::default();
+        collector.add(&channel("1"), &message(1, &["a.png"]), None);
+        collector.add(
+            &channel("1"),
+            &message(2, &["b.png"]),
+            at("2024-02-01T01:00:00+02:00"),
+        );
+        collector.add(
+            &channel("1"),
+            &message(3, &["c.png", "d.png"]),
+            at("2024-01-31T23:00:00-05:00"),
+        );
+        let stats = collector.finish();
+
+        let months: Vec<(&str, u32)> = stats
+            .by_month
+            .iter()
+            .map(|m| (m.month.as_str(), m.count))
+            .collect();
+        assert_eq!(months, [("2024-01", 2), ("2024-02", 1)]);
+        let order: Vec<&str> = stats
+            .attachments
+            .iter()
+            .map(|a| a.file_name.as_str())
+            .collect();
+        assert_eq!(order, ["b.png", "c.png", "d.png", "a.png"]);
+    }
+}
@@ -0,0 +1,120 @@
+use anyhow::Result;
+use serde_json::Value;
+use std::io::{Read, Seek};
+use zip::ZipArchive;
+
+use crate::models::{
+    AuditLogAction, AuditLogChange, AuditLogEntry, DAuditLog, DAuditLogEntry, DGuild,
+    GuildAuditLog, ModerationSummary,
+};
+use crate::parser::Parser;
+use crate::snowflake;
+
+impl<'a> Parser<'a> {
+    /// Reads `<servers_root>/<id>/audit-log.json`. Not gated on `DGuild::is_owner`: moderators
+    /// with audit log access get one in their package too, so the file's presence is what counts.
+    /// Entries that don't parse are skipped rather than dropping the whole log.
+    pub(super) fn load_audit_log<R: Read + Seek>(
+        &self,
+        archive: &mut ZipArchive<R>,
+        servers_root: &str,
+        guild: &DGuild,
+    ) -> Result<Option<GuildAuditLog>> {
+        let audit_log_path = format!("{}/{}/audit-log.json", servers_root, guild.id);
+        let Some(content) = self.read_file(archive, &audit_log_path)? else {
+            return Ok(None);
+        };
+
+        let raw_entries: Vec<DAuditLogEntry> = match self.parse_json::<Vec<Value>>(&content) {
+            Ok(items) => items
+                .into_iter()
+                .filter_map(|item| serde_json::from_value(item).ok())
+                .collect(),
+            Err(_) => match self.parse_json::<DAuditLog>(&content) {
+                Ok(log) => log.audit_log_entries,
+                Err(e) => {
+                    println!("[debug] Failed to parse audit log for {}: {}", guild.id, e);
+                    return Ok(None);
+                }
+            },
+        };
+
+        let mut entries: Vec<(Option<u64>, AuditLogEntry)> = raw_entries
+            .into_iter()
+            .map(|entry| {
+                let id = entry.id.trim().parse::<u64>().ok();
+                let entry = AuditLogEntry {
+                    action: AuditLogAction::from_code(entry.action_type),
+                    action_type: entry.action_type,
+                    user_id: entry.user_id,
+                    target_id: entry.target_id,
+                    reason: entry.reason,
+                    changes: entry
+                        .changes
+                        .into_iter()
+                        .map(|change| AuditLogChange {
+                            key: change.key,
+                            old_value: change.old_value.map(|v| v.to_string()),
+                            new_value: change.new_value.map(|v| v.to_string()),
+                        })
+                        .collect(),
+                    timestamp: id
+                        .and_then(snowflake::created_at)
+                        .map(|t| self.time_zone.localize(t).to_rfc3339()),
+                    id: entry.id,
+                };
+                (id, entry)
+            })
+            .collect();
+        entries.sort_by_key(|(id, _)| *id);
+        let entries: Vec<AuditLogEntry> = entries.into_iter().map(|(_, entry)| entry).collect();
+
+        Ok(Some(GuildAuditLog {
+            guild_id: guild.id.clone(),
+            guild_name: guild.name.clone(),
+            summary: moderation_summary(&entries),
+            entries,
+        }))
+    }
+}
+
+fn moderation_summary(entries: &[AuditLogEntry]) -> ModerationSummary {
+    let mut summary = ModerationSummary {
+        entry_count: entries.len() as u32,
+        first_entry: entries.iter().find_map(|e| e.timestamp.clone()),
+        last_entry: entries.iter().rev().find_map(|e| e.timestamp.clone()),
+        ..Default::default()
+    };
+
+    for entry in entries {
+        match entry.action {
+            AuditLogAction::MemberBanAdd => summary.ban_count += 1,
+            AuditLogAction::MemberBanRemove => summary.unban_count += 1,
+            AuditLogAction::MemberKick => summary.kick_count += 1,
+            AuditLogAction::MemberPrune => summary.prune_count += 1,
+            AuditLogAction::MemberUpdate
+                if entry
+                    .changes
+                    .iter()
+                    .any(|c| c.key == "communication_disabled_until" && c.new_value.is_some()) =>
+            {
+                summary.timeout_count += 1
+            }
+            AuditLogAction::MemberRoleUpdate
+            | AuditLogAction::RoleCreate
+            | AuditLogAction::RoleUpdate
+            | AuditLogAction::RoleDelete => summary.role_change_count += 1,
+            AuditLogAction::ChannelCreate
+            | AuditLogAction::ChannelUpdate
+            | AuditLogAction::ChannelDelete
+            | AuditLogAction::ChannelOverwriteCreate
+            | AuditLogAction::ChannelOverwriteUpdate
+            | AuditLogAction::ChannelOverwriteDelete => summary.channel_change_count += 1,
+            AuditLogAction::MessageDelete | AuditLogAction::MessageBulkDelete => {
+                summary.message_delete_count += 1
+            }
+            _ => {}
+        }
+    }
+    summary
+}
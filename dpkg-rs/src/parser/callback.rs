@@ -18,6 +18,11 @@ impl Callback {
         });
     }
 
+    pub fn warning(&self, step: Step, message: String) {
+        println!("[debug] Warning: {}", message);
+        self.progress(step, format!("Warning: {}", message));
+    }
+
     pub fn error(&self, step: Step, message: String, title: String) {
         self.observer.on_error(OnError {
             step: step.to_string(),
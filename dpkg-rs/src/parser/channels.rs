@@ -1,12 +1,27 @@
 use anyhow::Result;
 use chrono::Timelike;
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::{Read, Seek};
 use zip::ZipArchive;
 
-use crate::models::{DChannel, DMessage, Message, TopChannel, TopDM, UserData, WordCount};
+use crate::models::{
+    ChannelKind, ChannelKindCount, DChannel, DMessage, Message, TopChannel, TopDM, UserData,
+};
+use crate::parser::attachments::AttachmentCollector;
+use crate::parser::emojis::EmojiCollector;
+use crate::parser::friends::FriendCollector;
+use crate::parser::guilds::GuildCollector;
+use crate::parser::languages::LanguageCollector;
+use crate::parser::links::LinkCollector;
+use crate::parser::mentions::MentionCollector;
+use crate::parser::milestones::MilestoneCollector;
+use crate::parser::phrases::PhraseCollector;
+use crate::parser::style::StyleCollector;
+use crate::parser::timezone::LocalTimeZone;
+use crate::parser::utils::{limit_or_all, top_counts};
 use crate::parser::{Callback, Parser};
+use crate::snowflake;
 
 impl<'a> Parser<'a> {
     pub(super) fn load_channels<R: Read + Seek>(
@@ -28,8 +43,25 @@ impl<'a> Parser<'a> {
             self.detect_package_format(messages_root, &channel_ids);
 
         let mut word_counts: HashMap<String, u32> = HashMap::new();
+        let mut unparsed_timestamps = 0;
+        let mut estimated_timestamps = 0;
+        let mut attachments = AttachmentCollector::default();
+        let mut links = LinkCollector::default();
+        let mut mentions = MentionCollector::default();
+        let mut emojis = EmojiCollector::default();
+        let mut message_style = StyleCollector::default();
+        let mut milestones = MilestoneCollector::default();
+        let mut friends = FriendCollector::default();
+        let mut guilds = GuildCollector::default();
+        let mut phrases = self.options.compute_phrases.then(PhraseCollector::default);
+        let mut languages = self
+            .options
+            .detect_languages
+            .then(LanguageCollector::default);
         let mut channel_message_counts: Vec<TopChannel> = Vec::new();
         let mut dm_message_counts: Vec<TopDM> = Vec::new();
+        let mut kind_counts: HashMap<ChannelKind, (u32, u32)> = HashMap::new();
+        let mut forum_ids: HashSet<String> = HashSet::new();
 
         for (index, channel_id) in channel_ids.iter().enumerate() {
             self.check_cancellation_token()?;
@@ -62,20 +94,28 @@ impl<'a> Parser<'a> {
             let channel_data = self.read_file(archive, &channel_data_path)?;
             let channel_messages_content = self.read_file(archive, &channel_messages_path)?;
 
-            if let (Some(data_content), Some(messages_content)) =
-                (channel_data, channel_messages_content)
-            {
-                let channel: DChannel = match self.parse_json(&data_content) {
-                    Ok(ch) => ch,
+            let channel = channel_data.and_then(|data_content| {
+                match self.parse_json::<DChannel>(&data_content) {
+                    Ok(ch) => Some(ch),
                     Err(e) => {
                         println!(
                             "[debug] Failed to parse channel data for {}: {}",
                             channel_id, e
                         );
-                        continue;
+                        None
                     }
-                };
+                }
+            });
+
+            // Forums hold no messages of their own, but their channel.json tells us which
+            // threads are forum posts.
+            if let Some(channel) = &channel
+                && channel.kind() == ChannelKind::Forum
+            {
+                forum_ids.insert(channel.id.clone());
+            }
 
+            if let (Some(channel), Some(messages_content)) = (channel, channel_messages_content) {
                 let messages: Vec<Message> = if extension == "csv" {
                     self.parse_csv(&messages_content)?
                 } else {
@@ -93,7 +133,8 @@ impl<'a> Parser<'a> {
                 //     .map(|s| s.as_str())
                 //     .unwrap_or(&channel.id);
 
-                let is_dm = channel.recipients.as_ref().is_some_and(|r| r.len() == 2);
+                let kind = channel.kind();
+                let is_dm = kind == ChannelKind::Dm;
                 let dm_user_id = if is_dm {
                     channel.recipients.as_ref().and_then(|recipients| {
                         extracted_data
@@ -105,31 +146,73 @@ impl<'a> Parser<'a> {
                     None
                 };
 
+                mentions.add_channel(&channel);
+                milestones.add_channel(&channel);
+                if let Some(dm_id) = dm_user_id {
+                    friends.add_dm(&channel.id, dm_id);
+                }
+
+                let created_at = snowflake::created_at_str(&channel.id)
+                    .map(|t| self.time_zone.localize(t).to_rfc3339());
+
                 let mut message_count = 0;
                 for message in &messages {
                     self.check_cancellation_token()?;
                     message_count += 1;
                     extracted_data.character_count += message.length;
-                    if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(
-                        &message.timestamp,
-                        "%Y-%m-%d %H:%M:%S",
-                    ) {
-                        extracted_data.hours_values[dt.hour() as usize] += 1;
+                    if message.timestamp_from_id {
+                        estimated_timestamps += 1;
+                    }
+                    let local_time = message.timestamp.map(|dt| self.time_zone.localize(dt));
+                    match local_time {
+                        Some(local) => {
+                            extracted_data.hours_values[local.hour() as usize] += 1;
+                        }
+                        None => unparsed_timestamps += 1,
+                    }
+                    attachments.add(&channel, message, local_time);
+                    links.add(&channel, message);
+                    mentions.add(message);
+                    emojis.add(message);
+                    message_style.add(&channel, message, local_time);
+                    milestones.add(&channel, is_dm, message);
+                    guilds.add(&channel, message, local_time);
+                    if let Some(dm_id) = dm_user_id {
+                        friends.add(dm_id, local_time);
+                    }
+                    if let Some(phrases) = phrases.as_mut() {
+                        phrases.add(message);
+                    }
+                    if let Some(languages) = languages.as_mut() {
+                        languages.add(&channel, message, local_time);
                     }
 
                     if !&message.words.is_empty() {
-                        for word in message.words.iter().filter(|w| w.len() > 5) {
+                        for word in message.words.iter().filter(|w| self.is_countable_word(w)) {
                             *word_counts.entry(word.clone()).or_insert(0) += 1;
                         }
                     }
                 }
 
+                let kind_count = kind_counts.entry(kind).or_insert((0, 0));
+                kind_count.0 += 1;
+                kind_count.1 += message_count;
+
+                if let Some(phrases) = phrases.as_mut() {
+                    phrases.end_channel(
+                        &channel,
+                        self.options.roll_up_threads,
+                        self.options.top_words_limit,
+                    );
+                }
+
                 if is_dm {
                     if let Some(dm_id) = dm_user_id {
                         dm_message_counts.push(TopDM {
                             id: channel.id.clone(),
                             dm_user_id: dm_id.clone(),
                             message_count,
+                            created_at,
                         });
                     }
                 } else {
@@ -138,18 +221,57 @@ impl<'a> Parser<'a> {
                     } else {
                         (None, None)
                     };
-                    channel_message_counts.push(TopChannel {
+                    let top_channel = TopChannel {
                         id: channel.id.clone(),
                         name: channel.name.clone(),
                         r#type: channel.r#type.clone(),
+                        kind,
                         guild_name,
                         guild_id,
                         message_count,
-                    });
+                        created_at,
+                        parent_id: channel.parent_id.clone(),
+                        thread_count: 0,
+                    };
+                    channel_message_counts.push(top_channel);
                 }
             }
         }
 
+        mark_forum_posts(&mut channel_message_counts, &forum_ids, &mut kind_counts);
+        let mut orphaned_channels: Vec<TopChannel> = channel_message_counts
+            .iter()
+            .filter(|c| c.guild_id.is_none() && !c.kind.is_private())
+            .cloned()
+            .collect();
+
+        if estimated_timestamps > 0 {
+            callback.warning(
+                crate::parser::Step::Messages,
+                format!(
+                    "{} message timestamps were missing or could not be parsed and were taken from message IDs",
+                    estimated_timestamps
+                ),
+            );
+        }
+        if unparsed_timestamps > 0 {
+            callback.warning(
+                crate::parser::Step::Messages,
+                format!(
+                    "{} message timestamps could not be parsed and were left out of time statistics",
+                    unparsed_timestamps
+                ),
+            );
+        }
+
+        extracted_data.attachments = attachments.finish();
+        extracted_data.links = links.finish(self.options.top_list_limit);
+        extracted_data.mentions =
+            mentions.finish(extracted_data.user.as_ref(), self.options.top_list_limit);
+        extracted_data.emojis = emojis.finish(self.options.top_emotes_limit);
+        extracted_data.favorite_emotes = extracted_data.emojis.favorite_emotes();
+        extracted_data.message_style = message_style.finish();
+
         self.finalize_channel_stats(
             extracted_data,
             word_counts,
@@ -157,6 +279,59 @@ impl<'a> Parser<'a> {
             dm_message_counts,
         );
 
+        let known_guilds: HashSet<&str> = extracted_data
+            .guilds
+            .iter()
+            .map(|g| g.id.as_str())
+            .collect();
+        let mut guilds = guilds.finish();
+        for guild in &mut guilds {
+            guild.left = !known_guilds.contains(guild.id.as_str());
+        }
+        extracted_data.left_guilds = guilds.iter().filter(|g| g.left).cloned().collect();
+        extracted_data.top_guilds = guilds
+            .into_iter()
+            .take(limit_or_all(self.options.top_guilds_limit))
+            .collect();
+        let mut channel_kinds: Vec<ChannelKindCount> = kind_counts
+            .into_iter()
+            .map(|(kind, (channel_count, message_count))| ChannelKindCount {
+                kind,
+                channel_count,
+                message_count,
+            })
+            .collect();
+        channel_kinds.sort_by(|a, b| {
+            b.message_count
+                .cmp(&a.message_count)
+                .then_with(|| (a.kind as u8).cmp(&(b.kind as u8)))
+        });
+        extracted_data.channel_kinds = channel_kinds;
+
+        orphaned_channels.sort_by(|a, b| {
+            b.message_count
+                .cmp(&a.message_count)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        extracted_data.orphaned_channels = orphaned_channels;
+        println!(
+            "[debug] {} left guilds, {} orphaned channels",
+            extracted_data.left_guilds.len(),
+            extracted_data.orphaned_channels.len()
+        );
+
+        extracted_data.milestones = milestones.finish(
+            extracted_data.user.as_ref(),
+            &extracted_data.top_guilds,
+            &self.time_zone,
+        );
+        extracted_data.friends = friends.finish(extracted_data.user.as_ref(), &self.time_zone);
+        extracted_data.languages =
+            languages.map(|languages| languages.finish(self.options.top_channels_limit));
+        extracted_data.phrases = phrases.map(|phrases| {
+            phrases.finish(&extracted_data.top_channels, self.options.top_words_limit)
+        });
+
         Ok(())
     }
 
@@ -168,18 +343,19 @@ impl<'a> Parser<'a> {
             self.check_cancellation_token()?;
             let record: DMessage = result?;
 
-            let words = Parser::process_words(&record.contents);
+            let (words, phrase_breaks) = self.process_words(&record.contents);
+            let (timestamp, timestamp_from_id) =
+                Parser::message_timestamp(record.id, record.timestamp.as_deref());
             messages.push(Message {
                 id: record.id,
-                timestamp: record.timestamp,
+                timestamp,
+                timestamp_from_id,
                 content: record.contents.clone(),
-                attachments: record
-                    .attachments
-                    .split(' ')
-                    .map(|s| s.to_string())
-                    .collect(),
-                length: record.contents.len() as u32,
+                attachments: Parser::process_attachments(&record.attachments),
+                links: Parser::process_links(&record.contents),
+                length: record.contents.chars().count() as u32,
                 words,
+                phrase_breaks,
             });
         }
         Ok(messages)
@@ -196,14 +372,19 @@ impl<'a> Parser<'a> {
         Ok(messages
             .into_iter()
             .map(|m| {
-                let words = Parser::process_words(&m.contents);
+                let (words, phrase_breaks) = self.process_words(&m.contents);
+                let (timestamp, timestamp_from_id) =
+                    Parser::message_timestamp(m.id, m.timestamp.as_deref());
                 Message {
                     id: m.id,
                     content: m.contents.clone(),
-                    attachments: m.attachments.split(' ').map(|s| s.to_string()).collect(),
-                    timestamp: m.timestamp,
-                    length: m.contents.len() as u32,
+                    attachments: Parser::process_attachments(&m.attachments),
+                    links: Parser::process_links(&m.contents),
+                    timestamp,
+                    timestamp_from_id,
+                    length: m.contents.chars().count() as u32,
                     words,
+                    phrase_breaks,
                 }
             })
             .collect())
@@ -276,44 +457,343 @@ impl<'a> Parser<'a> {
                 .map(|c| c.message_count)
                 .sum::<u32>();
 
-        channel_message_counts.sort_by(|a, b| b.message_count.cmp(&a.message_count));
-        extracted_data.top_channels = channel_message_counts.into_iter().take(10).collect();
-
-        dm_message_counts.sort_by(|a, b| b.message_count.cmp(&a.message_count));
-        extracted_data.top_dms = dm_message_counts.into_iter().take(10).collect();
-
-        let emote_regex = Regex::new(r"<(a?):([^:]+):(\d+)>");
-        let mut word_vec: Vec<_> = word_counts.into_iter().collect();
-        word_vec.sort_by(|a, b| b.1.cmp(&a.1));
-
-        match emote_regex {
-            Ok(ref regex) => {
-                extracted_data.favorite_emotes = word_vec
-                    .iter()
-                    .filter(|word| regex.is_match(&word.0))
-                    .take(10)
-                    .map(|(word, count)| WordCount {
-                        word: word.clone(),
-                        count: *count,
-                    })
-                    .collect();
-
-                extracted_data.favorite_words = word_vec
-                    .into_iter()
-                    .filter(|word| !regex.is_match(&word.0))
-                    .take(10)
-                    .map(|(word, count)| WordCount { word, count })
-                    .collect();
+        if self.options.roll_up_threads {
+            channel_message_counts = roll_up_threads(channel_message_counts, &self.time_zone);
+        }
+        channel_message_counts.sort_by(|a, b| {
+            b.message_count
+                .cmp(&a.message_count)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        extracted_data.top_channels = channel_message_counts
+            .into_iter()
+            .take(limit_or_all(self.options.top_channels_limit))
+            .collect();
+
+        dm_message_counts.sort_by(|a, b| {
+            b.message_count
+                .cmp(&a.message_count)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        extracted_data.top_dms = dm_message_counts
+            .into_iter()
+            .take(limit_or_all(self.options.top_dms_limit))
+            .collect();
+
+        extracted_data.favorite_words = top_counts(word_counts, self.options.top_words_limit);
+    }
+}
+
+/// Re-labels threads created in one of `forum_ids` as forum posts, moving them to the
+/// `ForumPost` entry of `kind_counts`.
+fn mark_forum_posts(
+    channels: &mut [TopChannel],
+    forum_ids: &HashSet<String>,
+    kind_counts: &mut HashMap<ChannelKind, (u32, u32)>,
+) {
+    for channel in channels.iter_mut().filter(|c| {
+        c.kind == ChannelKind::Thread && c.parent_id.as_ref().is_some_and(|p| forum_ids.contains(p))
+    }) {
+        channel.kind = ChannelKind::ForumPost;
+        if let Some(threads) = kind_counts.get_mut(&ChannelKind::Thread) {
+            threads.0 -= 1;
+            threads.1 -= channel.message_count;
+            if threads.0 == 0 {
+                kind_counts.remove(&ChannelKind::Thread);
             }
-            Err(e) => {
-                println!("[debug] Failed to compile emote regex: {}", e);
-                extracted_data.favorite_emotes = Vec::new();
-                extracted_data.favorite_words = word_vec
-                    .into_iter()
-                    .take(10)
-                    .map(|(word, count)| WordCount { word, count })
-                    .collect();
+        }
+        let posts = kind_counts.entry(ChannelKind::ForumPost).or_insert((0, 0));
+        posts.0 += 1;
+        posts.1 += channel.message_count;
+    }
+}
+
+/// Merges threads with a known parent into the parent's entry. Parents the package has no
+/// messages for get a placeholder entry dated from their ID.
+fn roll_up_threads(channels: Vec<TopChannel>, time_zone: &LocalTimeZone) -> Vec<TopChannel> {
+    let (threads, mut channels): (Vec<TopChannel>, Vec<TopChannel>) = channels
+        .into_iter()
+        .partition(|c| c.kind.is_thread() && c.parent_id.is_some());
+
+    let mut positions: HashMap<String, usize> = channels
+        .iter()
+        .enumerate()
+        .map(|(position, c)| (c.id.clone(), position))
+        .collect();
+    for thread in threads {
+        let parent_id = thread.parent_id.clone().unwrap_or_default();
+        let position = *positions.entry(parent_id.clone()).or_insert_with(|| {
+            channels.push(TopChannel {
+                id: parent_id.clone(),
+                name: None,
+                r#type: None,
+                kind: if thread.kind == ChannelKind::ForumPost {
+                    ChannelKind::Forum
+                } else {
+                    ChannelKind::Unknown
+                },
+                message_count: 0,
+                guild_name: thread.guild_name.clone(),
+                guild_id: thread.guild_id.clone(),
+                created_at: snowflake::created_at_str(&parent_id)
+                    .map(|t| time_zone.localize(t).to_rfc3339()),
+                parent_id: None,
+                thread_count: 0,
+            });
+            channels.len() - 1
+        });
+        let parent = &mut channels[position];
+        parent.message_count += thread.message_count;
+        parent.thread_count += 1;
+    }
+    channels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{EventCount, ExtractObserver, OnError, OnProgress};
+    use std::io::{Cursor, Write};
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+    use zip::ZipWriter;
+    use zip::write::SimpleFileOptions;
+
+    fn channel(id: &str, kind: ChannelKind, parent_id: Option<&str>, count: u32) -> TopChannel {
+        TopChannel {
+            id: id.to_string(),
+            name: Some(format!("channel-{}", id)),
+            r#type: None,
+            kind,
+            message_count: count,
+            guild_name: Some("Guild".to_string()),
+            guild_id: Some("1".to_string()),
+            created_at: None,
+            parent_id: parent_id.map(str::to_string),
+            thread_count: 0,
+        }
+    }
+
+    #[test]
+    fn marks_threads_in_forums_as_forum_posts() {
+        let mut channels = vec![
+            channel("10", ChannelKind::Thread, Some("2"), 4),
+            channel("11", ChannelKind::Thread, Some("3"), 6),
+            channel("12", ChannelKind::Thread, Some("2"), 1),
+        ];
+        let forum_ids = HashSet::from(["2".to_string()]);
+        let mut kind_counts = HashMap::from([(ChannelKind::Thread, (3, 11))]);
+
+        mark_forum_posts(&mut channels, &forum_ids, &mut kind_counts);
+
+        let kinds: Vec<ChannelKind> = channels.iter().map(|c| c.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                ChannelKind::ForumPost,
+                ChannelKind::Thread,
+                ChannelKind::ForumPost
+            ]
+        );
+        assert_eq!(kind_counts[&ChannelKind::Thread], (1, 6));
+        assert_eq!(kind_counts[&ChannelKind::ForumPost], (2, 5));
+    }
+
+    #[test]
+    fn drops_the_thread_count_once_every_thread_is_a_forum_post() {
+        let mut channels = vec![channel("10", ChannelKind::Thread, Some("2"), 4)];
+        let forum_ids = HashSet::from(["2".to_string()]);
+        let mut kind_counts = HashMap::from([(ChannelKind::Thread, (1, 4))]);
+
+        mark_forum_posts(&mut channels, &forum_ids, &mut kind_counts);
+
+        assert!(!kind_counts.contains_key(&ChannelKind::Thread));
+        assert_eq!(kind_counts[&ChannelKind::ForumPost], (1, 4));
+    }
+
+    #[test]
+    fn rolls_threads_up_into_their_parent() {
+        let channels = vec![
+            channel("2", ChannelKind::Text, None, 5),
+            channel("10", ChannelKind::Thread, Some("2"), 4),
+            channel("11", ChannelKind::Thread, Some("2"), 3),
+            channel("12", ChannelKind::Thread, None, 2),
+        ];
+
+        let channels = roll_up_threads(channels, &LocalTimeZone::Utc);
+
+        let counts: Vec<(&str, u32, u32)> = channels
+            .iter()
+            .map(|c| (c.id.as_str(), c.message_count, c.thread_count))
+            .collect();
+        assert_eq!(counts, [("2", 12, 2), ("12", 2, 0)]);
+    }
+
+    #[test]
+    fn adds_a_placeholder_for_parents_without_messages() {
+        let channels = vec![
+            channel("10", ChannelKind::Thread, Some("175928847299117063"), 4),
+            channel("11", ChannelKind::ForumPost, Some("3"), 1),
+        ];
+
+        let channels = roll_up_threads(channels, &LocalTimeZone::Utc);
+
+        assert_eq!(channels.len(), 2);
+        let parent = &channels[0];
+        assert_eq!(parent.id, "175928847299117063");
+        assert_eq!(parent.name, None);
+        assert_eq!(parent.kind, ChannelKind::Unknown);
+        assert_eq!(parent.guild_id.as_deref(), Some("1"));
+        assert_eq!(
+            parent.created_at.as_deref(),
+            Some("2016-04-30T11:18:25.796+00:00")
+        );
+        assert_eq!((parent.message_count, parent.thread_count), (4, 1));
+        assert_eq!(channels[1].kind, ChannelKind::Forum);
+    }
+
+    struct NoopObserver;
+
+    impl ExtractObserver for NoopObserver {
+        fn on_progress(&self, _: OnProgress) {}
+        fn on_error(&self, _: OnError) {}
+        fn on_complete(&self, _: UserData) {}
+        fn on_analytics_complete(&self, _: EventCount) {}
+    }
+
+    fn package(files: &[(&str, &str)]) -> ZipArchive<Cursor<Vec<u8>>> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default();
+        for (path, content) in files {
+            if path.ends_with('/') {
+                writer.add_directory(*path, options).unwrap();
+            } else {
+                writer.start_file(*path, options).unwrap();
+                writer.write_all(content.as_bytes()).unwrap();
             }
         }
+        ZipArchive::new(writer.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn detects_left_guilds_and_orphaned_channels() {
+        let message = r#"[{"ID": 1, "Timestamp": "2021-01-05 10:00:00", "Contents": "hi", "Attachments": ""}]"#;
+        let mut archive = package(&[
+            (
+                "Account/user.json",
+                r#"{"id": "1", "username": "someone", "global_name": null, "discriminator": 0, "avatar_hash": null}"#,
+            ),
+            (
+                "Servers/index.json",
+                r#"{"1000000000000000001": "Still Here"}"#,
+            ),
+            (
+                "Servers/1000000000000000001/guild.json",
+                r#"{"id": "1000000000000000001", "name": "Still Here"}"#,
+            ),
+            ("Messages/c2000000000000000001/", ""),
+            (
+                "Messages/c2000000000000000001/channel.json",
+                r#"{"id": "2000000000000000001", "type": 0, "guild": {"id": "1000000000000000001", "name": "Still Here"}}"#,
+            ),
+            ("Messages/c2000000000000000001/messages.json", message),
+            ("Messages/c2000000000000000002/", ""),
+            (
+                "Messages/c2000000000000000002/channel.json",
+                r#"{"id": "2000000000000000002", "type": 0, "guild": {"id": "1000000000000000002", "name": "Gone"}}"#,
+            ),
+            ("Messages/c2000000000000000002/messages.json", message),
+            ("Messages/c2000000000000000003/", ""),
+            (
+                "Messages/c2000000000000000003/channel.json",
+                r#"{"id": "2000000000000000003", "type": 0}"#,
+            ),
+            ("Messages/c2000000000000000003/messages.json", message),
+            ("Messages/c2000000000000000004/", ""),
+            (
+                "Messages/c2000000000000000004/channel.json",
+                r#"{"id": "2000000000000000004", "type": 1, "recipients": ["1", "5"]}"#,
+            ),
+            ("Messages/c2000000000000000004/messages.json", message),
+        ]);
+
+        let cancellation_token = Arc::new(AtomicBool::new(false));
+        let mut parser = Parser::new(&cancellation_token, Default::default());
+        let callback = Callback::new(Arc::new(NoopObserver));
+        let data = parser.process_data(&mut archive, &callback).unwrap();
+
+        let left: Vec<_> = data.left_guilds.iter().map(|g| g.id.as_str()).collect();
+        assert_eq!(left, ["1000000000000000002"]);
+        let still_here = data
+            .top_guilds
+            .iter()
+            .find(|g| g.id == "1000000000000000001")
+            .unwrap();
+        assert!(!still_here.left);
+
+        let orphaned: Vec<_> = data
+            .orphaned_channels
+            .iter()
+            .map(|c| c.id.as_str())
+            .collect();
+        assert_eq!(orphaned, ["2000000000000000003"]);
+    }
+
+    #[test]
+    fn tells_dms_from_group_dms_and_finds_forum_posts() {
+        let message = r#"[{"ID": 1, "Timestamp": "2021-01-05 10:00:00", "Contents": "hi", "Attachments": ""}]"#;
+        let mut archive = package(&[
+            (
+                "Account/user.json",
+                r#"{"id": "1", "username": "someone", "global_name": null, "discriminator": 0, "avatar_hash": null}"#,
+            ),
+            (
+                "Servers/1000000000000000001/guild.json",
+                r#"{"id": "1000000000000000001", "name": "Guild"}"#,
+            ),
+            ("Messages/c2000000000000000001/", ""),
+            (
+                "Messages/c2000000000000000001/channel.json",
+                r#"{"id": "2000000000000000001", "type": 1, "recipients": ["1", "5"]}"#,
+            ),
+            ("Messages/c2000000000000000001/messages.json", message),
+            ("Messages/c2000000000000000002/", ""),
+            (
+                "Messages/c2000000000000000002/channel.json",
+                r#"{"id": "2000000000000000002", "type": 3, "recipients": ["1", "6"]}"#,
+            ),
+            ("Messages/c2000000000000000002/messages.json", message),
+            ("Messages/c2000000000000000003/", ""),
+            (
+                "Messages/c2000000000000000003/channel.json",
+                r#"{"id": "2000000000000000003", "type": 15, "guild": {"id": "1000000000000000001", "name": "Guild"}}"#,
+            ),
+            ("Messages/c2000000000000000004/", ""),
+            (
+                "Messages/c2000000000000000004/channel.json",
+                r#"{"id": "2000000000000000004", "type": 11, "parent_id": "2000000000000000003", "guild": {"id": "1000000000000000001", "name": "Guild"}}"#,
+            ),
+            ("Messages/c2000000000000000004/messages.json", message),
+        ]);
+
+        let cancellation_token = Arc::new(AtomicBool::new(false));
+        let mut parser = Parser::new(&cancellation_token, Default::default());
+        let callback = Callback::new(Arc::new(NoopObserver));
+        let data = parser.process_data(&mut archive, &callback).unwrap();
+
+        let dms: Vec<_> = data
+            .top_dms
+            .iter()
+            .map(|dm| (dm.id.as_str(), dm.dm_user_id.as_str()))
+            .collect();
+        assert_eq!(dms, [("2000000000000000001", "5")]);
+
+        let kinds: Vec<_> = data
+            .top_channels
+            .iter()
+            .map(|c| (c.id.as_str(), c.kind))
+            .collect();
+        assert!(kinds.contains(&("2000000000000000002", ChannelKind::GroupDm)));
+        assert!(kinds.contains(&("2000000000000000004", ChannelKind::ForumPost)));
     }
 }
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use crate::models::ExtractionOptions;
+
+/// Units of each currency per US dollar, as of March 2024. `ExtractionOptions.exchange_rates`
+/// overrides or extends this table.
+const BUNDLED_RATES: [(&str, f64); 40] = [
+    ("AED", 3.67),
+    ("ARS", 850.0),
+    ("AUD", 1.52),
+    ("BGN", 1.80),
+    ("BRL", 4.98),
+    ("CAD", 1.35),
+    ("CHF", 0.89),
+    ("CLP", 965.0),
+    ("COP", 3_900.0),
+    ("CZK", 23.3),
+    ("DKK", 6.87),
+    ("EUR", 0.92),
+    ("GBP", 0.79),
+    ("HKD", 7.82),
+    ("HUF", 362.0),
+    ("IDR", 15_700.0),
+    ("ILS", 3.62),
+    ("INR", 83.0),
+    ("JPY", 150.0),
+    ("KRW", 1_330.0),
+    ("MXN", 16.8),
+    ("MYR", 4.72),
+    ("NOK", 10.6),
+    ("NZD", 1.65),
+    ("PEN", 3.72),
+    ("PHP", 55.9),
+    ("PLN", 3.97),
+    ("RON", 4.58),
+    ("RUB", 92.0),
+    ("SAR", 3.75),
+    ("SEK", 10.4),
+    ("SGD", 1.34),
+    ("THB", 35.8),
+    ("TRY", 32.0),
+    ("TWD", 31.6),
+    ("UAH", 38.5),
+    ("USD", 1.0),
+    ("UYU", 39.0),
+    ("VND", 24_700.0),
+    ("ZAR", 18.9),
+];
+
+/// ISO 4217 currencies without cents. Every currency not listed here or in
+/// `THREE_DECIMAL_CURRENCIES` has two decimals.
+const ZERO_DECIMAL_CURRENCIES: [&str; 17] = [
+    "BIF", "CLP", "DJF", "GNF", "ISK", "JPY", "KMF", "KRW", "PYG", "RWF", "UGX", "UYI", "VND",
+    "VUV", "XAF", "XOF", "XPF",
+];
+
+const THREE_DECIMAL_CURRENCIES: [&str; 7] = ["BHD", "IQD", "JOD", "KWD", "LYD", "OMR", "TND"];
+
+/// Number of decimals in a currency's minor unit, e.g. 2 for USD and 0 for JPY.
+pub(super) fn minor_units(currency: &str) -> u32 {
+    if ZERO_DECIMAL_CURRENCIES.contains(&currency) {
+        0
+    } else if THREE_DECIMAL_CURRENCIES.contains(&currency) {
+        3
+    } else {
+        2
+    }
+}
+
+pub(super) struct CurrencyConverter {
+    target: String,
+    rates: HashMap<String, f64>,
+}
+
+impl CurrencyConverter {
+    /// `None` when no target currency is set, or when there's no rate for it.
+    pub(super) fn new(options: &ExtractionOptions) -> Option<Self> {
+        let target = options.target_currency.as_ref()?.trim().to_uppercase();
+
+        let mut rates: HashMap<String, f64> = BUNDLED_RATES
+            .iter()
+            .map(|(currency, rate)| (currency.to_string(), *rate))
+            .collect();
+        for rate in &options.exchange_rates {
+            if rate.units_per_usd.is_finite() && rate.units_per_usd > 0.0 {
+                rates.insert(rate.currency.trim().to_uppercase(), rate.units_per_usd);
+            }
+        }
+
+        if !rates.contains_key(&target) {
+            println!("[debug] No exchange rate for target currency {}", target);
+            return None;
+        }
+        Some(Self { target, rates })
+    }
+
+    pub(super) fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Converts an amount in `currency`'s minor units into the target's minor units.
+    pub(super) fn convert(&self, currency: &str, amount: i64) -> Option<i64> {
+        if currency == self.target {
+            return Some(amount);
+        }
+        let from_rate = self.rates.get(currency)?;
+        let to_rate = self.rates[&self.target];
+
+        let major = amount as f64 / 10f64.powi(minor_units(currency) as i32);
+        let converted = major / from_rate * to_rate;
+        Some((converted * 10f64.powi(minor_units(&self.target) as i32)).round() as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ExchangeRate;
+
+    fn converter(target: &str, rates: &[(&str, f64)]) -> Option<CurrencyConverter> {
+        CurrencyConverter::new(&ExtractionOptions {
+            target_currency: Some(target.to_string()),
+            exchange_rates: rates
+                .iter()
+                .map(|(currency, units_per_usd)| ExchangeRate {
+                    currency: currency.to_string(),
+                    units_per_usd: *units_per_usd,
+                })
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn knows_minor_units() {
+        assert_eq!(minor_units("USD"), 2);
+        assert_eq!(minor_units("EUR"), 2);
+        assert_eq!(minor_units("JPY"), 0);
+        assert_eq!(minor_units("KRW"), 0);
+        assert_eq!(minor_units("KWD"), 3);
+        assert_eq!(minor_units("BHD"), 3);
+    }
+
+    #[test]
+    fn converts_into_zero_decimal_currencies() {
+        let converter = converter("jpy", &[]).unwrap();
+        assert_eq!(converter.target(), "JPY");
+        // $9.99 at 150 JPY per dollar is 1498.5 yen, rounded to 1499.
+        assert_eq!(converter.convert("USD", 999), Some(1499));
+        assert_eq!(converter.convert("JPY", 1050), Some(1050));
+    }
+
+    #[test]
+    fn converts_from_zero_decimal_currencies() {
+        let converter = converter("USD", &[]).unwrap();
+        // ¥1500 is $10.00, i.e. 1000 cents.
+        assert_eq!(converter.convert("JPY", 1500), Some(1000));
+    }
+
+    #[test]
+    fn handles_three_decimal_currencies() {
+        let to_kwd = converter("KWD", &[("KWD", 0.3)]).unwrap();
+        // $10.00 at 0.3 KWD per dollar is 3.000 KWD, i.e. 3000 fils.
+        assert_eq!(to_kwd.convert("USD", 1000), Some(3000));
+
+        let from_kwd = converter("USD", &[("kwd", 0.3)]).unwrap();
+        assert_eq!(from_kwd.convert("KWD", 3000), Some(1000));
+    }
+
+    #[test]
+    fn overrides_bundled_rates() {
+        let converter = converter("EUR", &[("EUR", 0.5)]).unwrap();
+        assert_eq!(converter.convert("USD", 1000), Some(500));
+    }
+
+    #[test]
+    fn ignores_invalid_override_rates() {
+        for rate in [0.0, -1.0, f64::INFINITY, f64::NAN] {
+            let converter = converter("EUR", &[("EUR", rate)]).unwrap();
+            assert_eq!(converter.convert("USD", 1000), Some(920));
+        }
+    }
+
+    #[test]
+    fn skips_unknown_currencies() {
+        let to_usd = converter("USD", &[]).unwrap();
+        assert_eq!(to_usd.convert("XYZ", 1000), None);
+        assert!(converter("XYZ", &[]).is_none());
+        assert!(CurrencyConverter::new(&ExtractionOptions::default()).is_none());
+    }
+}
@@ -0,0 +1,190 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+use unicode_properties::emoji::{EmojiStatus, UnicodeEmoji, is_regional_indicator};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::models::{CustomEmoteCount, EmojiStats, Message, WordCount};
+use crate::parser::utils::{limit_or_all, top_counts};
+
+lazy_static! {
+    pub(super) static ref CUSTOM_EMOTE_REGEX: Regex =
+        Regex::new(r"<(a?):([A-Za-z0-9_~]+):(\d+)>").unwrap();
+}
+
+#[derive(Default)]
+pub(super) struct EmojiCollector {
+    custom: HashMap<String, CustomEmoteCount>,
+    unicode: HashMap<String, u32>,
+}
+
+impl EmojiCollector {
+    pub(super) fn add(&mut self, message: &Message) {
+        for captures in CUSTOM_EMOTE_REGEX.captures_iter(&message.content) {
+            let id = captures[3].to_string();
+            let emote = self
+                .custom
+                .entry(id.clone())
+                .or_insert_with(|| CustomEmoteCount {
+                    id,
+                    name: captures[2].to_string(),
+                    animated: &captures[1] == "a",
+                    count: 0,
+                });
+            emote.count += 1;
+        }
+
+        if message.content.is_ascii() {
+            return;
+        }
+        for grapheme in message.content.graphemes(true) {
+            if is_emoji_grapheme(grapheme) {
+                *self.unicode.entry(grapheme.to_string()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    pub(super) fn finish(self, limit: Option<u32>) -> EmojiStats {
+        let mut custom: Vec<CustomEmoteCount> = self.custom.into_values().collect();
+        custom.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.id.cmp(&b.id)));
+
+        let animated_count = custom.iter().filter(|e| e.animated).map(|e| e.count).sum();
+        let static_count = custom.iter().filter(|e| !e.animated).map(|e| e.count).sum();
+
+        EmojiStats {
+            custom_count: animated_count + static_count,
+            animated_count,
+            static_count,
+            unicode_count: self.unicode.values().sum(),
+            top_custom: custom.into_iter().take(limit_or_all(limit)).collect(),
+            top_unicode: top_counts(self.unicode, limit),
+        }
+    }
+}
+
+impl EmojiStats {
+    /// Custom emotes in their `<a:name:id>` message form, as used by `favorite_emotes`.
+    pub(super) fn favorite_emotes(&self) -> Vec<WordCount> {
+        self.top_custom
+            .iter()
+            .map(|emote| WordCount {
+                word: format!(
+                    "<{}:{}:{}>",
+                    if emote.animated { "a" } else { "" },
+                    emote.name,
+                    emote.id
+                ),
+                count: emote.count,
+            })
+            .collect()
+    }
+}
+
+/// Whether a grapheme cluster renders as an emoji: flags, keycaps, emoji-presentation
+/// characters and text-default symbols promoted by VS16, a skin tone or a ZWJ sequence.
+pub(super) fn is_emoji_grapheme(grapheme: &str) -> bool {
+    let Some(first) = grapheme.chars().next() else {
+        return false;
+    };
+    if grapheme.contains('\u{20E3}') {
+        return true;
+    }
+    if is_regional_indicator(first) {
+        return grapheme
+            .chars()
+            .filter(|c| is_regional_indicator(*c))
+            .count()
+            == 2;
+    }
+    match first.emoji_status() {
+        EmojiStatus::EmojiPresentation
+        | EmojiStatus::EmojiPresentationAndModifierBase
+        | EmojiStatus::EmojiPresentationAndModifierAndEmojiComponent => true,
+        EmojiStatus::EmojiModifierBase | EmojiStatus::EmojiOther => {
+            !grapheme.contains('\u{FE0E}')
+                && grapheme
+                    .chars()
+                    .any(|c| matches!(c, '\u{FE0F}' | '\u{200D}' | '\u{1F3FB}'..='\u{1F3FF}'))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emojis(content: &str) -> EmojiStats {
+        let mut collector = EmojiCollector::default();
+        collector.add(&Message {
+            id: 1,
+            timestamp: None,
+            timestamp_from_id: false,
+            content: content.to_string(),
+            attachments: Vec::new(),
+            links: Vec::new(),
+            length: content.chars().count() as u32,
+            words: Vec::new(),
+            phrase_breaks: Vec::new(),
+        });
+        collector.finish(None)
+    }
+
+    fn unicode(content: &str) -> Vec<String> {
+        let mut found: Vec<String> = emojis(content)
+            .top_unicode
+            .into_iter()
+            .map(|e| e.word)
+            .collect();
+        found.sort();
+        found
+    }
+
+    #[test]
+    fn counts_zwj_sequences_as_one_emoji() {
+        assert_eq!(
+            unicode("family: \u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}"),
+            ["\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}"]
+        );
+        assert_eq!(
+            unicode("\u{2764}\u{FE0F}\u{200D}\u{1F525}"),
+            ["\u{2764}\u{FE0F}\u{200D}\u{1F525}"]
+        );
+    }
+
+    #[test]
+    fn keeps_skin_tones_with_their_base() {
+        assert_eq!(unicode("\u{1F44D}\u{1F3FD}"), ["\u{1F44D}\u{1F3FD}"]);
+        // Victory hand is text-default, the modifier makes it an emoji.
+        assert_eq!(unicode("\u{270C}\u{1F3FD}"), ["\u{270C}\u{1F3FD}"]);
+    }
+
+    #[test]
+    fn counts_flags_and_keycaps() {
+        assert_eq!(unicode("\u{1F1EB}\u{1F1F7}"), ["\u{1F1EB}\u{1F1F7}"]);
+        assert_eq!(
+            unicode("1\u{FE0F}\u{20E3} #\u{20E3}"),
+            ["#\u{20E3}", "1\u{FE0F}\u{20E3}"]
+        );
+        assert!(unicode("a lone \u{1F1EB} 1 #").is_empty());
+    }
+
+    #[test]
+    fn needs_vs16_for_text_presentation_symbols() {
+        assert!(unicode("\u{2714} \u{2764} \u{263A} \u{2122} \u{2194} \u{00A9}").is_empty());
+        assert!(unicode("\u{263A}\u{FE0E}").is_empty());
+        assert_eq!(unicode("\u{2764}\u{FE0F}"), ["\u{2764}\u{FE0F}"]);
+        assert_eq!(unicode("\u{1F600}"), ["\u{1F600}"]);
+    }
+
+    #[test]
+    fn counts_custom_emotes_glued_to_text() {
+        let stats = emojis("hi<:wave:123>there<a:dance:456><:wave:123>!");
+        assert_eq!(stats.custom_count, 3);
+        assert_eq!(stats.animated_count, 1);
+        assert_eq!(stats.top_custom[0].name, "wave");
+        assert_eq!(stats.top_custom[0].count, 2);
+        assert_eq!(stats.unicode_count, 0);
+        assert_eq!(stats.favorite_emotes()[1].word, "<a:dance:456>");
+    }
+}
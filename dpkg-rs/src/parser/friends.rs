@@ -0,0 +1,150 @@
+use chrono::{DateTime, FixedOffset};
+use std::collections::HashMap;
+
+use crate::models::{DUser, DmContact, FriendStats, MonthCount, RelationshipKind};
+use crate::parser::Parser;
+use crate::parser::timezone::LocalTimeZone;
+
+#[derive(Default)]
+pub(super) struct FriendCollector {
+    dms: HashMap<String, DmActivity>,
+}
+
+#[derive(Default)]
+struct DmActivity {
+    channel_id: String,
+    message_count: u32,
+    first: Option<DateTime<FixedOffset>>,
+    last: Option<DateTime<FixedOffset>>,
+    months: HashMap<String, u32>,
+}
+
+impl FriendCollector {
+    pub(super) fn add_dm(&mut self, channel_id: &str, user_id: &str) {
+        self.dms
+            .entry(user_id.to_string())
+            .or_insert_with(|| DmActivity {
+                channel_id: channel_id.to_string(),
+                ..Default::default()
+            });
+    }
+
+    pub(super) fn add(&mut self, user_id: &str, local_time: Option<DateTime<FixedOffset>>) {
+        let Some(activity) = self.dms.get_mut(user_id) else {
+            return;
+        };
+        activity.message_count += 1;
+        let Some(local) = local_time else {
+            return;
+        };
+        if activity.first.is_none_or(|first| local < first) {
+            activity.first = Some(local);
+        }
+        if activity.last.is_none_or(|last| local > last) {
+            activity.last = Some(local);
+        }
+        *activity
+            .months
+            .entry(local.format("%Y-%m").to_string())
+            .or_insert(0) += 1;
+    }
+
+    pub(super) fn finish(mut self, user: Option<&DUser>, time_zone: &LocalTimeZone) -> FriendStats {
+        let mut stats = FriendStats::default();
+
+        for relationship in user.into_iter().flat_map(|u| u.relationships.iter()) {
+            let activity = self.dms.remove(&relationship.user.id);
+            let mut contact = contact(relationship.user.id.clone(), activity);
+            contact.username = Some(relationship.user.username.clone());
+            contact.global_name = relationship.user.global_name.clone();
+            contact.nickname = relationship.nickname.clone();
+            contact.relationship = Some(relationship.r#type);
+            contact.since = relationship
+                .since
+                .as_deref()
+                .and_then(Parser::parse_timestamp)
+                .map(|t| time_zone.localize(t).to_rfc3339());
+
+            if relationship.r#type != RelationshipKind::Friend {
+                if contact.dm_channel_id.is_some() {
+                    stats.non_friends.push(contact);
+                }
+            } else if contact.message_count == 0 {
+                stats.never_messaged.push(contact);
+            } else {
+                stats.friends.push(contact);
+            }
+        }
+        stats.non_friends.extend(
+            self.dms
+                .into_iter()
+                .map(|(user_id, activity)| contact(user_id, Some(activity))),
+        );
+
+        for contacts in [&mut stats.friends, &mut stats.non_friends] {
+            contacts.sort_by(|a, b| {
+                b.message_count
+                    .cmp(&a.message_count)
+                    .then_with(|| a.user_id.cmp(&b.user_id))
+            });
+        }
+        stats
+    }
+}
+
+fn contact(user_id: String, activity: Option<DmActivity>) -> DmContact {
+    let activity = activity.unwrap_or_default();
+    let busiest_month = activity
+        .months
+        .into_iter()
+        .max_by(|(a_month, a_count), (b_month, b_count)| {
+            a_count.cmp(b_count).then_with(|| b_month.cmp(a_month))
+        })
+        .map(|(month, count)| MonthCount { month, count });
+
+    DmContact {
+        user_id,
+        username: None,
+        global_name: None,
+        nickname: None,
+        relationship: None,
+        since: None,
+        dm_channel_id: (!activity.channel_id.is_empty()).then_some(activity.channel_id),
+        message_count: activity.message_count,
+        first_dm: activity.first.map(|t| t.to_rfc3339()),
+        last_dm: activity.last.map(|t| t.to_rfc3339()),
+        busiest_month,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn localizes_friend_since_dates() {
+        let user: DUser = serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "username": "someone",
+            "global_name": null,
+            "discriminator": 0,
+            "avatar_hash": null,
+            "relationships": [
+                {"id": "2", "type": 1, "since": "2020-05-01T23:30:00.123000+00:00",
+                 "user": {"id": "2", "username": "friend", "global_name": null, "discriminator": "0", "avatar": null}},
+                {"id": "3", "type": 1, "since": "not a date",
+                 "user": {"id": "3", "username": "other", "global_name": null, "discriminator": "0", "avatar": null}}
+            ]
+        }))
+        .unwrap();
+        let time_zone = LocalTimeZone::Named(chrono_tz::Europe::Paris);
+
+        let stats = FriendCollector::default().finish(Some(&user), &time_zone);
+        let since: Vec<Option<&str>> = stats
+            .never_messaged
+            .iter()
+            .map(|c| c.since.as_deref())
+            .collect();
+        assert_eq!(since, [Some("2020-05-02T01:30:00.123+02:00"), None]);
+    }
+}
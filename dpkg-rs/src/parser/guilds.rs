@@ -0,0 +1,153 @@
+use chrono::{DateTime, FixedOffset, Timelike};
+use std::collections::{HashMap, HashSet};
+
+use crate::models::{DChannel, Message, TopGuild};
+
+#[derive(Default)]
+pub(super) struct GuildCollector {
+    guilds: HashMap<String, GuildActivity>,
+}
+
+struct GuildActivity {
+    name: String,
+    message_count: u32,
+    character_count: u32,
+    channels: HashSet<String>,
+    first: Option<DateTime<FixedOffset>>,
+    last: Option<DateTime<FixedOffset>>,
+    hours_values: Vec<u32>,
+}
+
+impl GuildCollector {
+    pub(super) fn add(
+        &mut self,
+        channel: &DChannel,
+        message: &Message,
+        local_time: Option<DateTime<FixedOffset>>,
+    ) {
+        let Some(guild) = &channel.guild else {
+            return;
+        };
+        let activity = self
+            .guilds
+            .entry(guild.id.clone())
+            .or_insert_with(|| GuildActivity {
+                name: guild.name.clone(),
+                message_count: 0,
+                character_count: 0,
+                channels: HashSet::new(),
+                first: None,
+                last: None,
+                hours_values: vec![0; 24],
+            });
+        activity.message_count += 1;
+        activity.character_count += message.length;
+        if !activity.channels.contains(&channel.id) {
+            activity.channels.insert(channel.id.clone());
+        }
+
+        let Some(local) = local_time else {
+            return;
+        };
+        activity.hours_values[local.hour() as usize] += 1;
+        if activity.first.is_none_or(|first| local < first) {
+            activity.first = Some(local);
+        }
+        if activity.last.is_none_or(|last| local > last) {
+            activity.last = Some(local);
+        }
+    }
+
+    /// Every guild with messages, most active first.
+    pub(super) fn finish(self) -> Vec<TopGuild> {
+        let mut guilds: Vec<TopGuild> = self
+            .guilds
+            .into_iter()
+            .map(|(id, activity)| TopGuild {
+                id,
+                name: activity.name,
+                message_count: activity.message_count,
+                character_count: activity.character_count,
+                active_channel_count: activity.channels.len() as u32,
+                first_message: activity.first.map(|t| t.to_rfc3339()),
+                last_message: activity.last.map(|t| t.to_rfc3339()),
+                hours_values: activity.hours_values,
+                left: false,
+            })
+            .collect();
+        guilds.sort_by(|a, b| {
+            b.message_count
+                .cmp(&a.message_count)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        guilds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DGuild;
+
+    fn channel(id: &str, guild_id: &str) -> DChannel {
+        DChannel {
+            id: id.to_string(),
+            name: None,
+            r#type: Some("0".to_string()),
+            recipients: None,
+            guild: Some(DGuild {
+                id: guild_id.to_string(),
+                name: format!("guild-{}", guild_id),
+                ..Default::default()
+            }),
+            parent_id: None,
+        }
+    }
+
+    fn add_messages(collector: &mut GuildCollector, channel: &DChannel, count: usize) {
+        let message = Message {
+            id: 1,
+            timestamp: None,
+            timestamp_from_id: false,
+            content: "hi".to_string(),
+            attachments: Vec::new(),
+            links: Vec::new(),
+            length: 2,
+            words: Vec::new(),
+            phrase_breaks: Vec::new(),
+        };
+        for _ in 0..count {
+            collector.add(channel, &message, None);
+        }
+    }
+
+    #[test]
+    fn ranks_guilds_by_message_count_then_id() {
+        let mut collector = GuildCollector::default();
+        add_messages(&mut collector, &channel("10", "3"), 2);
+        add_messages(&mut collector, &channel("14", "1"), 1);
+        add_messages(&mut collector, &channel("11", "1"), 1);
+        add_messages(&mut collector, &channel("12", "2"), 2);
+        add_messages(&mut collector, &channel("13", "2"), 1);
+
+        let guilds = collector.finish();
+        let ranking: Vec<_> = guilds
+            .iter()
+            .map(|g| (g.id.as_str(), g.message_count))
+            .collect();
+        assert_eq!(ranking, [("2", 3), ("1", 2), ("3", 2)]);
+        assert_eq!(guilds[0].active_channel_count, 2);
+        assert_eq!(guilds[0].character_count, 6);
+    }
+
+    #[test]
+    fn ignores_messages_outside_guilds() {
+        let mut collector = GuildCollector::default();
+        let dm = DChannel {
+            guild: None,
+            ..channel("10", "1")
+        };
+        add_messages(&mut collector, &dm, 1);
+        assert!(collector.finish().is_empty());
+    }
+}
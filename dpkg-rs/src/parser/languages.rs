@@ -0,0 +1,185 @@
+use chrono::{DateTime, Datelike, FixedOffset};
+use std::collections::{BTreeMap, HashMap};
+use whatlang::Lang;
+
+use crate::models::{
+    ChannelLanguages, DChannel, LanguageCount, LanguageStats, Message, YearLanguages,
+};
+use crate::parser::utils::limit_or_all;
+use crate::parser::words::strip_markup;
+
+/// Shorter texts are mostly greetings and reactions that can't be identified reliably.
+const MIN_DETECTION_LENGTH: usize = 15;
+
+#[derive(Default)]
+pub(super) struct LanguageCollector {
+    overall: HashMap<Lang, u32>,
+    by_channel: HashMap<String, (Option<String>, HashMap<Lang, u32>)>,
+    by_year: BTreeMap<i32, HashMap<Lang, u32>>,
+}
+
+impl LanguageCollector {
+    pub(super) fn add(
+        &mut self,
+        channel: &DChannel,
+        message: &Message,
+        local_time: Option<DateTime<FixedOffset>>,
+    ) {
+        let text = strip_markup(&message.content);
+        if text.chars().filter(|c| c.is_alphabetic()).count() < MIN_DETECTION_LENGTH {
+            return;
+        }
+        let Some(info) = whatlang::detect(&text).filter(|info| info.is_reliable()) else {
+            return;
+        };
+        let lang = info.lang();
+
+        *self.overall.entry(lang).or_insert(0) += 1;
+        *self
+            .by_channel
+            .entry(channel.id.clone())
+            .or_insert_with(|| (channel.name.clone(), HashMap::new()))
+            .1
+            .entry(lang)
+            .or_insert(0) += 1;
+        if let Some(local) = local_time {
+            *self
+                .by_year
+                .entry(local.year())
+                .or_default()
+                .entry(lang)
+                .or_insert(0) += 1;
+        }
+    }
+
+    pub(super) fn finish(self, channel_limit: Option<u32>) -> LanguageStats {
+        let mut by_channel: Vec<ChannelLanguages> = self
+            .by_channel
+            .into_iter()
+            .map(|(channel_id, (channel_name, counts))| ChannelLanguages {
+                channel_id,
+                channel_name,
+                detected_count: counts.values().sum(),
+                languages: language_counts(counts),
+            })
+            .collect();
+        by_channel.sort_by(|a, b| {
+            b.detected_count
+                .cmp(&a.detected_count)
+                .then_with(|| a.channel_id.cmp(&b.channel_id))
+        });
+        by_channel.truncate(limit_or_all(channel_limit));
+
+        LanguageStats {
+            detected_count: self.overall.values().sum(),
+            by_language: language_counts(self.overall),
+            by_channel,
+            by_year: self
+                .by_year
+                .into_iter()
+                .map(|(year, counts)| YearLanguages {
+                    year,
+                    detected_count: counts.values().sum(),
+                    languages: language_counts(counts),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn language_counts(counts: HashMap<Lang, u32>) -> Vec<LanguageCount> {
+    let mut counts: Vec<LanguageCount> = counts
+        .into_iter()
+        .map(|(lang, count)| LanguageCount {
+            code: lang.code().to_string(),
+            name: lang.eng_name().to_string(),
+            count,
+        })
+        .collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.code.cmp(&b.code)));
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(id: &str) -> DChannel {
+        DChannel {
+            id: id.to_string(),
+            name: Some(format!("channel-{}", id)),
+            r#type: None,
+            recipients: None,
+            guild: None,
+            parent_id: None,
+        }
+    }
+
+    fn add(collector: &mut LanguageCollector, channel_id: &str, content: &str, time: Option<&str>) {
+        collector.add(
+            &channel(channel_id),
+            &Message {
+                id: 1,
+                timestamp: None,
+                timestamp_from_id: false,
+                content: content.to_string(),
+                attachments: Vec::new(),
+                links: Vec::new(),
+                length: content.chars().count() as u32,
+                words: Vec::new(),
+                phrase_breaks: Vec::new(),
+            },
+            time.and_then(|t| DateTime::parse_from_rfc3339(t).ok()),
+        );
+    }
+
+    const ENGLISH: &str = "The weather is really nice today and we are going to the beach";
+    const GERMAN: &str = "Das Wetter ist heute wirklich schön und wir gehen an den Strand";
+
+    #[test]
+    fn needs_fifteen_alphabetic_characters() {
+        let mut collector = LanguageCollector::default();
+        // 14 letters, padded with digits, punctuation and a link that don't count.
+        add(
+            &mut collector,
+            "1",
+            "abcdefg hijklmn 12345 !!! https://example.com/abcdef",
+            None,
+        );
+        assert_eq!(collector.finish(None).detected_count, 0);
+
+        let mut collector = LanguageCollector::default();
+        add(&mut collector, "1", ENGLISH, None);
+        assert_eq!(collector.finish(None).detected_count, 1);
+    }
+
+    #[test]
+    fn counts_by_language_channel_and_local_year() {
+        let mut collector = LanguageCollector::default();
+        add(
+            &mut collector,
+            "1",
+            ENGLISH,
+            Some("2023-12-31T23:30:00-05:00"),
+        );
+        add(&mut collector, "1", ENGLISH, Some("2024-06-01T12:00:00Z"));
+        add(&mut collector, "2", GERMAN, None);
+        let stats = collector.finish(Some(1));
+
+        assert_eq!(stats.detected_count, 3);
+        let languages: Vec<(&str, u32)> = stats
+            .by_language
+            .iter()
+            .map(|l| (l.code.as_str(), l.count))
+            .collect();
+        assert_eq!(languages, [("eng", 2), ("deu", 1)]);
+        assert_eq!(stats.by_channel.len(), 1);
+        assert_eq!(stats.by_channel[0].channel_id, "1");
+        let years: Vec<(i32, u32)> = stats
+            .by_year
+            .iter()
+            .map(|y| (y.year, y.detected_count))
+            .collect();
+        assert_eq!(years, [(2023, 1), (2024, 1)]);
+    }
+}
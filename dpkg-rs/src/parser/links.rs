@@ -0,0 +1,227 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::{ChannelCount, DChannel, LinkStats, Message};
+use crate::parser::Parser;
+use crate::parser::utils::{limit_or_all, top_counts};
+
+lazy_static! {
+    /// `http(s)://` links, plus invites pasted without a scheme such as `discord.gg/abc`.
+    pub(super) static ref LINK_REGEX: Regex = Regex::new(
+        r"https?://[^\s<>]+|\b(?:(?:www\.)?discord\.gg|(?:(?:www|ptb|canary)\.)?discord(?:app)?\.com/invite)/[^\s<>]+"
+    )
+    .unwrap();
+}
+
+/// Hosts that serve invites under `/invite/<code>`.
+const INVITE_PATH_HOSTS: [&str; 4] = [
+    "discord.com",
+    "discordapp.com",
+    "ptb.discord.com",
+    "canary.discord.com",
+];
+
+impl<'a> Parser<'a> {
+    pub(crate) fn process_links(content: &str) -> Vec<String> {
+        LINK_REGEX
+            .find_iter(content)
+            .map(|m| {
+                m.as_str()
+                    .trim_end_matches(['.', ',', ')', '!', '?', ';', ':', '\'', '"', '*', '_', '~'])
+                    .to_string()
+            })
+            .collect()
+    }
+}
+
+#[derive(Default)]
+pub(super) struct LinkCollector {
+    total_count: u32,
+    domains: HashMap<String, u32>,
+    links: HashMap<String, u32>,
+    invites: HashMap<String, u32>,
+    by_channel: HashMap<String, ChannelCount>,
+}
+
+impl LinkCollector {
+    pub(super) fn add(&mut self, channel: &DChannel, message: &Message) {
+        if message.links.is_empty() {
+            return;
+        }
+
+        let channel_entry = self
+            .by_channel
+            .entry(channel.id.clone())
+            .or_insert_with(|| ChannelCount {
+                channel_id: channel.id.clone(),
+                channel_name: channel.name.clone(),
+                count: 0,
+            });
+        channel_entry.count += message.links.len() as u32;
+
+        for link in &message.links {
+            self.total_count += 1;
+
+            let (host, path) = split_link(link);
+            if let Some(invite) = invite_code(&host, path) {
+                *self
+                    .invites
+                    .entry(format!("discord.gg/{}", invite))
+                    .or_insert(0) += 1;
+                continue;
+            }
+
+            *self.links.entry(link.clone()).or_insert(0) += 1;
+            if !host.is_empty() {
+                *self.domains.entry(host).or_insert(0) += 1;
+            }
+        }
+    }
+
+    pub(super) fn finish(self, limit: Option<u32>) -> LinkStats {
+        let mut by_channel: Vec<ChannelCount> = self.by_channel.into_values().collect();
+        by_channel.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.channel_id.cmp(&b.channel_id))
+        });
+
+        LinkStats {
+            total_count: self.total_count,
+            invite_count: self.invites.values().sum(),
+            top_domains: top_counts(self.domains, limit),
+            top_links: top_counts(self.links, limit),
+            top_invites: top_counts(self.invites, limit),
+            by_channel: by_channel.into_iter().take(limit_or_all(limit)).collect(),
+        }
+    }
+}
+
+/// Splits a link into its lowercased host (without `www.`, credentials or port) and its path.
+fn split_link(link: &str) -> (String, &str) {
+    let rest = link.split_once("://").map(|(_, rest)| rest).unwrap_or(link);
+    let end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let (authority, path) = rest.split_at(end);
+    let host = authority.rsplit('@').next().unwrap_or(authority);
+    let host = host.split(':').next().unwrap_or(host).to_lowercase();
+    let host = host
+        .strip_prefix("www.")
+        .map(str::to_string)
+        .unwrap_or(host);
+    (host, path)
+}
+
+fn invite_code(host: &str, path: &str) -> Option<String> {
+    let mut segments = path
+        .split(['?', '#'])
+        .next()
+        .unwrap_or(path)
+        .split('/')
+        .filter(|s| !s.is_empty());
+    let is_invite = host == "discord.gg"
+        || (INVITE_PATH_HOSTS.contains(&host) && segments.next() == Some("invite"));
+    if is_invite {
+        segments.next().map(str::to_string)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+
+    fn links(contents: &[&str]) -> LinkStats {
+        let channel = DChannel {
+            id: "1".to_string(),
+            name: None,
+            r#type: None,
+            recipients: None,
+            guild: None,
+            parent_id: None,
+        };
+        let mut collector = LinkCollector::default();
+        for content in contents {
+            collector.add(
+                &channel,
+                &Message {
+                    id: 1,
+                    timestamp: None,
+                    timestamp_from_id: false,
+                    content: content.to_string(),
+                    attachments: Vec::new(),
+                    links: Parser::process_links(content),
+                    length: content.chars().count() as u32,
+                    words: Vec::new(),
+                    phrase_breaks: Vec::new(),
+                },
+            );
+        }
+        collector.finish(None)
+    }
+
+    fn counts(counts: &[crate::models::WordCount]) -> Vec<(&str, u32)> {
+        counts.iter().map(|c| (c.word.as_str(), c.count)).collect()
+    }
+
+    #[test]
+    fn trims_trailing_punctuation() {
+        assert_eq!(
+            Parser::process_links("see (https://example.com/a), or https://x.org/b!"),
+            ["https://example.com/a", "https://x.org/b"]
+        );
+    }
+
+    #[test]
+    fn normalizes_domains() {
+        let stats = links(&[
+            "https://www.Example.com/a https://example.com:8080/b",
+            "http://user@example.com?q=1 https://docs.rs/regex",
+        ]);
+        assert_eq!(
+            counts(&stats.top_domains),
+            [("example.com", 3), ("docs.rs", 1)]
+        );
+        assert_eq!(stats.total_count, 4);
+    }
+
+    #[test]
+    fn extracts_invite_codes_without_query_or_fragment() {
+        let stats = links(&[
+            "https://discord.gg/abc?event=1 https://discord.com/invite/abc#top",
+            "https://canary.discord.com/invite/xyz/",
+        ]);
+        assert_eq!(
+            counts(&stats.top_invites),
+            [("discord.gg/abc", 2), ("discord.gg/xyz", 1)]
+        );
+        assert_eq!(stats.invite_count, 3);
+        assert!(stats.top_domains.is_empty());
+        assert!(stats.top_links.is_empty());
+    }
+
+    #[test]
+    fn extracts_invites_without_a_scheme() {
+        let stats = links(&["join discord.gg/abc or www.discord.com/invite/def, mydiscord.gg/x"]);
+        assert_eq!(
+            counts(&stats.top_invites),
+            [("discord.gg/abc", 1), ("discord.gg/def", 1)]
+        );
+        assert_eq!(stats.total_count, 2);
+    }
+
+    #[test]
+    fn keeps_links_out_of_words() {
+        let cancellation_token = Arc::new(AtomicBool::new(false));
+        let parser = Parser::new(&cancellation_token, Default::default());
+        assert_eq!(
+            parser
+                .process_words("look https://example.com/cats here discord.gg/dogs")
+                .0,
+            ["look"]
+        );
+    }
+}
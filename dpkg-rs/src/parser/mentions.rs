@@ -0,0 +1,156 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::{DChannel, DUser, MentionCount, MentionStats, Message};
+use crate::parser::utils::limit_or_all;
+
+lazy_static! {
+    pub(super) static ref MENTION_REGEX: Regex =
+        Regex::new(r"<(@!?|@&|#)(\d+)>|(?:^|[^\w@])@(everyone|here)\b").unwrap();
+}
+
+#[derive(Default)]
+pub(super) struct MentionCollector {
+    total_count: u32,
+    everyone_count: u32,
+    here_count: u32,
+    users: HashMap<String, u32>,
+    roles: HashMap<String, u32>,
+    channels: HashMap<String, u32>,
+    channel_names: HashMap<String, String>,
+}
+
+impl MentionCollector {
+    pub(super) fn add_channel(&mut self, channel: &DChannel) {
+        if let Some(name) = &channel.name {
+            self.channel_names.insert(channel.id.clone(), name.clone());
+        }
+    }
+
+    pub(super) fn add(&mut self, message: &Message) {
+        for captures in MENTION_REGEX.captures_iter(&message.content) {
+            self.total_count += 1;
+            if let Some(target) = captures.get(3) {
+                match target.as_str() {
+                    "everyone" => self.everyone_count += 1,
+                    _ => self.here_count += 1,
+                }
+                continue;
+            }
+
+            let id = captures[2].to_string();
+            let counts = match &captures[1] {
+                "@&" => &mut self.roles,
+                "#" => &mut self.channels,
+                _ => &mut self.users,
+            };
+            *counts.entry(id).or_insert(0) += 1;
+        }
+    }
+
+    pub(super) fn finish(self, user: Option<&DUser>, limit: Option<u32>) -> MentionStats {
+        let user_names: HashMap<&str, &str> = user
+            .map(|u| {
+                u.relationships
+                    .iter()
+                    .map(|r| {
+                        (
+                            r.user.id.as_str(),
+                            r.user
+                                .global_name
+                                .as_deref()
+                                .unwrap_or(r.user.username.as_str()),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        MentionStats {
+            total_count: self.total_count,
+            everyone_count: self.everyone_count,
+            here_count: self.here_count,
+            top_users: top_mentions(self.users, limit, |id| {
+                user_names.get(id).map(|name| name.to_string())
+            }),
+            top_roles: top_mentions(self.roles, limit, |_| None),
+            top_channels: top_mentions(self.channels, limit, |id| {
+                self.channel_names.get(id).cloned()
+            }),
+        }
+    }
+}
+
+fn top_mentions(
+    counts: HashMap<String, u32>,
+    limit: Option<u32>,
+    resolve_name: impl Fn(&str) -> Option<String>,
+) -> Vec<MentionCount> {
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+        .into_iter()
+        .take(limit_or_all(limit))
+        .map(|(id, count)| MentionCount {
+            name: resolve_name(&id),
+            id,
+            count,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::words::strip_markup;
+
+    fn mentions(content: &str) -> MentionStats {
+        let mut collector = MentionCollector::default();
+        collector.add(&Message {
+            id: 1,
+            timestamp: None,
+            timestamp_from_id: false,
+            content: content.to_string(),
+            attachments: Vec::new(),
+            links: Vec::new(),
+            length: content.chars().count() as u32,
+            words: Vec::new(),
+            phrase_breaks: Vec::new(),
+        });
+        collector.finish(None, None)
+    }
+
+    #[test]
+    fn counts_everyone_and_here() {
+        let stats = mentions("@everyone look, (@here) and @here!");
+        assert_eq!(stats.everyone_count, 1);
+        assert_eq!(stats.here_count, 2);
+        assert_eq!(stats.total_count, 3);
+    }
+
+    #[test]
+    fn ignores_email_addresses_and_mid_word_at_signs() {
+        let stats = mentions("mail bob@here.com or x@everyone, not @@here");
+        assert_eq!(stats.everyone_count, 0);
+        assert_eq!(stats.here_count, 0);
+        assert_eq!(stats.total_count, 0);
+    }
+
+    #[test]
+    fn counts_user_role_and_channel_mentions() {
+        let stats = mentions("<@123> <@!123> <@&456> <#789>");
+        assert_eq!(stats.total_count, 4);
+        assert_eq!(stats.top_users[0].id, "123");
+        assert_eq!(stats.top_users[0].count, 2);
+        assert_eq!(stats.top_roles[0].id, "456");
+        assert_eq!(stats.top_channels[0].id, "789");
+    }
+
+    #[test]
+    fn strip_markup_keeps_email_addresses() {
+        let stripped = strip_markup("write to bob@here.com @here now");
+        assert!(stripped.contains("bob@here.com"));
+        assert!(!stripped.contains(" @here"));
+    }
+}
@@ -0,0 +1,337 @@
+use chrono::{DateTime, Utc};
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::models::{
+    CountMilestone, DChannel, DGuild, DUser, Message, MessageMilestone, Milestones,
+    RelationshipKind, RelationshipMilestone, TopGuild,
+};
+use crate::parser::Parser;
+use crate::parser::timezone::LocalTimeZone;
+use crate::snowflake;
+
+/// Message counts whose dates are reported in `message_count_milestones`.
+const MESSAGE_COUNT_MILESTONES: [u32; 3] = [1_000, 10_000, 100_000];
+
+/// Only this many of the earliest timestamps are needed to date every count milestone.
+const MAX_MILESTONE_COUNT: usize =
+    MESSAGE_COUNT_MILESTONES[MESSAGE_COUNT_MILESTONES.len() - 1] as usize;
+
+#[derive(Default)]
+pub(super) struct MilestoneCollector {
+    first_message: Option<(DateTime<Utc>, u64, String)>,
+    first_dm: Option<(DateTime<Utc>, u64, String)>,
+    last_message: Option<(DateTime<Utc>, u64, String)>,
+    first_guild_messages: HashMap<String, (DateTime<Utc>, u64, String)>,
+    channels: HashMap<String, (Option<String>, Option<DGuild>)>,
+    /// The earliest message timestamps, in microseconds. Capped at `MAX_MILESTONE_COUNT`
+    /// entries by evicting the latest, so memory doesn't grow with the package.
+    earliest_timestamps: BinaryHeap<i64>,
+}
+
+impl MilestoneCollector {
+    pub(super) fn add_channel(&mut self, channel: &DChannel) {
+        self.channels.insert(
+            channel.id.clone(),
+            (channel.name.clone(), channel.guild.clone()),
+        );
+    }
+
+    pub(super) fn add(&mut self, channel: &DChannel, is_dm: bool, message: &Message) {
+        let Some(timestamp) = message.timestamp else {
+            return;
+        };
+        let micros = timestamp.timestamp_micros();
+        if self.earliest_timestamps.len() < MAX_MILESTONE_COUNT {
+            self.earliest_timestamps.push(micros);
+        } else if self
+            .earliest_timestamps
+            .peek()
+            .is_some_and(|&latest| micros < latest)
+        {
+            self.earliest_timestamps.pop();
+            self.earliest_timestamps.push(micros);
+        }
+
+        let candidate = || (timestamp, message.id, channel.id.clone());
+        if self
+            .first_message
+            .as_ref()
+            .is_none_or(|(first, ..)| timestamp < *first)
+        {
+            self.first_message = Some(candidate());
+        }
+        if self
+            .last_message
+            .as_ref()
+            .is_none_or(|(last, ..)| timestamp > *last)
+        {
+            self.last_message = Some(candidate());
+        }
+        if is_dm
+            && self
+                .first_dm
+                .as_ref()
+                .is_none_or(|(first, ..)| timestamp < *first)
+        {
+            self.first_dm = Some(candidate());
+        }
+        if let Some(guild) = &channel.guild {
+            let first = self
+                .first_guild_messages
+                .entry(guild.id.clone())
+                .or_insert_with(candidate);
+            if timestamp < first.0 {
+                *first = candidate();
+            }
+        }
+    }
+
+    pub(super) fn finish(
+        mut self,
+        user: Option<&DUser>,
+        top_guilds: &[TopGuild],
+        time_zone: &LocalTimeZone,
+    ) -> Milestones {
+        let account_created_at = user.and_then(|u| snowflake::created_at_str(&u.id));
+
+        let earliest_timestamps = std::mem::take(&mut self.earliest_timestamps).into_sorted_vec();
+        let message_count_milestones = MESSAGE_COUNT_MILESTONES
+            .iter()
+            .filter_map(|&count| {
+                let timestamp =
+                    DateTime::from_timestamp_micros(*earliest_timestamps.get(count as usize - 1)?)?;
+                Some(CountMilestone {
+                    count,
+                    timestamp: time_zone.localize(timestamp).to_rfc3339(),
+                })
+            })
+            .collect();
+
+        let oldest_relationship = user
+            .into_iter()
+            .flat_map(|u| u.relationships.iter())
+            .filter(|r| r.r#type == RelationshipKind::Friend)
+            .filter_map(|r| {
+                let since = Parser::parse_timestamp(r.since.as_deref()?)?;
+                Some((since, r))
+            })
+            .min_by_key(|(since, _)| *since)
+            .map(|(since, r)| RelationshipMilestone {
+                user_id: r.user.id.clone(),
+                username: r.user.username.clone(),
+                global_name: r.user.global_name.clone(),
+                since: time_zone.localize(since).to_rfc3339(),
+            });
+
+        Milestones {
+            account_created_at: account_created_at
+                .map(|created_at| time_zone.localize(created_at).to_rfc3339()),
+            account_age_days: account_created_at
+                .map(|created_at| (Utc::now() - created_at).num_days().max(0) as u32),
+            first_message: self.milestone(self.first_message.as_ref(), time_zone),
+            first_dm: self.milestone(self.first_dm.as_ref(), time_zone),
+            last_message: self.milestone(self.last_message.as_ref(), time_zone),
+            first_guild_messages: top_guilds
+                .iter()
+                .filter_map(|g| self.milestone(self.first_guild_messages.get(&g.id), time_zone))
+                .collect(),
+            oldest_relationship,
+            message_count_milestones,
+        }
+    }
+
+    fn milestone(
+        &self,
+        entry: Option<&(DateTime<Utc>, u64, String)>,
+        time_zone: &LocalTimeZone,
+    ) -> Option<MessageMilestone> {
+        let (timestamp, message_id, channel_id) = entry?;
+        let (channel_name, guild) = self.channels.get(channel_id)?;
+        Some(MessageMilestone {
+            message_id: message_id.to_string(),
+            channel_id: channel_id.clone(),
+            channel_name: channel_name.clone(),
+            guild_id: guild.as_ref().map(|g| g.id.clone()),
+            guild_name: guild.as_ref().map(|g| g.name.clone()),
+            timestamp: time_zone.localize(*timestamp).to_rfc3339(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn guild(id: &str) -> DGuild {
+        DGuild {
+            id: id.to_string(),
+            name: format!("guild-{}", id),
+            ..Default::default()
+        }
+    }
+
+    fn channel(id: &str, guild: Option<DGuild>) -> DChannel {
+        DChannel {
+            id: id.to_string(),
+            name: Some(format!("channel-{}", id)),
+            r#type: None,
+            recipients: None,
+            guild,
+            parent_id: None,
+        }
+    }
+
+    fn message(id: u64, timestamp: DateTime<Utc>) -> Message {
+        Message {
+            id,
+            timestamp: Some(timestamp),
+            timestamp_from_id: false,
+            content: String::new(),
+            attachments: Vec::new(),
+            links: Vec::new(),
+            length: 0,
+            words: Vec::new(),
+            phrase_breaks: Vec::new(),
+        }
+    }
+
+    fn top_guild(id: &str) -> TopGuild {
+        TopGuild {
+            id: id.to_string(),
+            name: format!("guild-{}", id),
+            message_count: 0,
+            character_count: 0,
+            active_channel_count: 0,
+            first_message: None,
+            last_message: None,
+            hours_values: Vec::new(),
+            left: false,
+        }
+    }
+
+    fn day(day: u32) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(&format!("2024-01-{:02}T12:00:00Z", day))
+            .unwrap()
+            .to_utc()
+    }
+
+    #[test]
+    fn finds_first_last_and_first_dm_messages() {
+        let guild_channel = channel("1", Some(guild("10")));
+        let other_channel = channel("2", Some(guild("20")));
+        let dm = channel("3", None);
+        let mut collector = MilestoneCollector::default();
+        for channel in [&guild_channel, &other_channel, &dm] {
+            collector.add_channel(channel);
+        }
+        collector.add(&guild_channel, false, &message(1, day(5)));
+        collector.add(&guild_channel, false, &message(2, day(2)));
+        collector.add(&other_channel, false, &message(3, day(9)));
+        collector.add(&dm, true, &message(4, day(7)));
+        collector.add(&dm, true, &message(5, day(3)));
+
+        let milestones = collector.finish(
+            None,
+            &[top_guild("20"), top_guild("10")],
+            &LocalTimeZone::Utc,
+        );
+        let first = milestones.first_message.unwrap();
+        assert_eq!(first.message_id, "2");
+        assert_eq!(first.guild_name.as_deref(), Some("guild-10"));
+        assert_eq!(first.timestamp, "2024-01-02T12:00:00+00:00");
+        assert_eq!(milestones.last_message.unwrap().message_id, "3");
+        assert_eq!(milestones.first_dm.unwrap().message_id, "5");
+        let guild_firsts: Vec<String> = milestones
+            .first_guild_messages
+            .into_iter()
+            .map(|m| m.message_id)
+            .collect();
+        assert_eq!(guild_firsts, ["3", "2"]);
+        assert!(milestones.message_count_milestones.is_empty());
+        assert_eq!(milestones.account_created_at, None);
+    }
+
+    #[test]
+    fn dates_count_milestones_in_chronological_order() {
+        let channel = channel("1", None);
+        let mut collector = MilestoneCollector::default();
+        collector.add_channel(&channel);
+        let start = day(1);
+        // Added newest first, so the 1,000th message is the 1,000th oldest, not the 1,000th seen.
+        for i in (0..10_500).rev() {
+            collector.add(
+                &channel,
+                false,
+                &message(i, start + Duration::minutes(i as i64)),
+            );
+        }
+
+        let milestones = collector.finish(None, &[], &LocalTimeZone::Utc);
+        let counts: Vec<(u32, String)> = milestones
+            .message_count_milestones
+            .into_iter()
+            .map(|m| (m.count, m.timestamp))
+            .collect();
+        assert_eq!(
+            counts,
+            [
+                (1_000, (start + Duration::minutes(999)).to_rfc3339()),
+                (10_000, (start + Duration::minutes(9_999)).to_rfc3339()),
+            ]
+        );
+    }
+
+    #[test]
+    fn caps_the_timestamps_it_keeps() {
+        let channel = channel("1", None);
+        let mut collector = MilestoneCollector::default();
+        for i in 0..(MAX_MILESTONE_COUNT as u64 + 50) {
+            collector.add(
+                &channel,
+                false,
+                &message(i, day(1) + Duration::seconds(i as i64)),
+            );
+        }
+        assert_eq!(collector.earliest_timestamps.len(), MAX_MILESTONE_COUNT);
+        assert_eq!(
+            collector.earliest_timestamps.peek(),
+            Some(&(day(1) + Duration::seconds(MAX_MILESTONE_COUNT as i64 - 1)).timestamp_micros())
+        );
+    }
+
+    #[test]
+    fn decodes_account_age_and_oldest_friend() {
+        let user: DUser = serde_json::from_value(serde_json::json!({
+            "id": "175928847299117063",
+            "username": "someone",
+            "global_name": null,
+            "discriminator": 0,
+            "avatar_hash": null,
+            "relationships": [
+                {"id": "1", "type": 1, "since": "2020-05-01T00:00:00+00:00",
+                 "user": {"id": "1", "username": "newer", "global_name": null, "discriminator": "0", "avatar": null}},
+                {"id": "2", "type": 2, "since": "2016-01-01T00:00:00+00:00",
+                 "user": {"id": "2", "username": "blocked", "global_name": null, "discriminator": "0", "avatar": null}},
+                {"id": "3", "type": 1, "since": "2018-03-04T05:06:07+00:00",
+                 "user": {"id": "3", "username": "older", "global_name": null, "discriminator": "0", "avatar": null}}
+            ]
+        }))
+        .unwrap();
+
+        let milestones =
+            MilestoneCollector::default().finish(Some(&user), &[], &LocalTimeZone::Utc);
+        assert_eq!(
+            milestones.account_created_at.as_deref(),
+            Some("2016-04-30T11:18:25.796+00:00")
+        );
+        let created_at = snowflake::created_at(175928847299117063).unwrap();
+        assert_eq!(
+            milestones.account_age_days,
+            Some((Utc::now() - created_at).num_days() as u32)
+        );
+        let oldest = milestones.oldest_relationship.unwrap();
+        assert_eq!(oldest.username, "older");
+        assert_eq!(oldest.since, "2018-03-04T05:06:07+00:00");
+    }
+}
@@ -1,30 +1,55 @@
+mod activities;
 mod analytics;
+mod attachments;
+mod audit_log;
 mod callback;
 mod channels;
+mod currency;
+mod emojis;
+mod friends;
+mod guilds;
+mod languages;
+mod links;
+mod mentions;
+mod milestones;
+mod payments;
+mod phrases;
 mod servers;
+mod stop_words;
+mod style;
+mod timezone;
 mod user;
 mod utils;
+mod words;
 
 use anyhow::{Result, anyhow};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::Read;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use zip::ZipArchive;
 
-use crate::models::UserData;
+use crate::models::{ExtractionOptions, UserData};
 pub use crate::parser::callback::*;
+use crate::parser::timezone::LocalTimeZone;
+use crate::parser::words::stop_word_set;
 
 pub struct Parser<'a> {
     pub(crate) file_index: HashMap<String, usize>,
     pub(crate) cancellation_token: &'a Arc<AtomicBool>,
+    pub(crate) options: ExtractionOptions,
+    pub(crate) time_zone: LocalTimeZone,
+    pub(crate) stop_words: HashSet<String>,
 }
 
 impl<'a> Parser<'a> {
-    pub fn new(cancellation_token: &'a Arc<AtomicBool>) -> Self {
+    pub fn new(cancellation_token: &'a Arc<AtomicBool>, options: ExtractionOptions) -> Self {
         Self {
             file_index: HashMap::new(),
             cancellation_token,
+            stop_words: stop_word_set(&options),
+            options,
+            time_zone: LocalTimeZone::Utc,
         }
     }
 
@@ -51,9 +76,12 @@ impl<'a> Parser<'a> {
         println!("[debug] Found servers root: {}", servers_root);
         println!("[debug] Found user root: {}", user_root);
 
+        self.time_zone = self.resolve_time_zone(archive, callback)?;
+
         self.load_user(archive, &user_root, &mut extracted_data, callback)?;
-        self.load_channels(archive, &messages_root, &mut extracted_data, callback)?;
+        // Servers go first so channels can be checked against the guilds the user is still in.
         self.load_servers(archive, &servers_root, &mut extracted_data, callback)?;
+        self.load_channels(archive, &messages_root, &mut extracted_data, callback)?;
 
         callback.progress(Step::Messages, "Finalizing extraction...".to_string());
         println!("[debug] Extraction complete");
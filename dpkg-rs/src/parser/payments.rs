@@ -0,0 +1,427 @@
+use chrono::{DateTime, Datelike, FixedOffset, Months};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::models::{
+    CurrencyAmount, DPayment, NitroGap, NitroPeriod, Payment, PaymentStats, PaymentStatus,
+    ProductKind, ProductSpending, StatusSummary, YearSpending,
+};
+use crate::parser::Parser;
+use crate::parser::currency::{CurrencyConverter, minor_units};
+
+/// Renewals are charged at the end of a period but can land a few days late.
+const NITRO_RENEWAL_GRACE_DAYS: i64 = 7;
+
+/// Sums per currency, keyed by uppercase code.
+#[derive(Default)]
+struct CurrencyTotals(BTreeMap<String, (i64, u32)>);
+
+impl CurrencyTotals {
+    fn add(&mut self, currency: &str, amount: i64) {
+        let total = self.0.entry(currency.to_string()).or_insert((0, 0));
+        total.0 += amount;
+        total.1 += 1;
+    }
+
+    /// Sum in the converter's currency, skipping currencies it has no rate for.
+    fn converted(&self, converter: Option<&CurrencyConverter>) -> Option<i64> {
+        let converter = converter?;
+        Some(
+            self.0
+                .iter()
+                .filter_map(|(currency, (amount, _))| converter.convert(currency, *amount))
+                .sum(),
+        )
+    }
+
+    fn into_vec(self) -> Vec<CurrencyAmount> {
+        self.0
+            .into_iter()
+            .map(|(currency, (amount, count))| CurrencyAmount {
+                minor_units: minor_units(&currency),
+                currency,
+                amount,
+                count,
+            })
+            .collect()
+    }
+}
+
+impl<'a> Parser<'a> {
+    pub(super) fn payment_stats(&self, payments: &[DPayment]) -> PaymentStats {
+        let converter = CurrencyConverter::new(&self.options);
+        let converter = converter.as_ref();
+
+        let mut parsed: Vec<(Option<DateTime<FixedOffset>>, &DPayment, Payment)> = payments
+            .iter()
+            .map(|p| {
+                let created_at =
+                    Parser::parse_timestamp(&p.created_at).map(|t| self.time_zone.localize(t));
+                let currency = p.currency.trim().to_uppercase();
+                let payment = Payment {
+                    description: p.description.clone(),
+                    kind: product_kind(&p.description),
+                    status: PaymentStatus::from_code(p.status),
+                    minor_units: minor_units(&currency),
+                    converted_amount: converter.and_then(|c| c.convert(&currency, p.amount)),
+                    currency,
+                    amount: p.amount,
+                    created_at: created_at.map(|t| t.to_rfc3339()),
+                };
+                (created_at, p, payment)
+            })
+            .collect();
+        // Undated payments go last rather than first.
+        parsed.sort_by_key(|(created_at, ..)| (created_at.is_none(), *created_at));
+
+        let mut totals = CurrencyTotals::default();
+        let mut by_year: BTreeMap<i32, CurrencyTotals> = BTreeMap::new();
+        let mut by_product: HashMap<&str, (ProductKind, CurrencyTotals)> = HashMap::new();
+        let mut by_status: BTreeMap<PaymentStatus, CurrencyTotals> = BTreeMap::new();
+        let mut nitro_payments = Vec::new();
+
+        for (created_at, raw, payment) in &parsed {
+            by_status
+                .entry(payment.status)
+                .or_default()
+                .add(&payment.currency, payment.amount);
+            if payment.status != PaymentStatus::Completed {
+                continue;
+            }
+
+            totals.add(&payment.currency, payment.amount);
+            by_product
+                .entry(&raw.description)
+                .or_insert_with(|| (payment.kind, CurrencyTotals::default()))
+                .1
+                .add(&payment.currency, payment.amount);
+            if let Some(created_at) = created_at {
+                by_year
+                    .entry(created_at.year())
+                    .or_default()
+                    .add(&payment.currency, payment.amount);
+                if payment.kind.is_nitro_subscription() {
+                    nitro_payments.push((*created_at, payment.kind, &raw.description));
+                }
+            }
+        }
+
+        let mut by_product: Vec<ProductSpending> = by_product
+            .into_iter()
+            .map(|(description, (kind, totals))| ProductSpending {
+                description: description.to_string(),
+                kind,
+                converted_total: totals.converted(converter),
+                totals: totals.into_vec(),
+            })
+            .collect();
+        by_product.sort_by_cached_key(|p| {
+            (
+                std::cmp::Reverse(p.totals.iter().map(|t| t.count).sum::<u32>()),
+                p.description.clone(),
+            )
+        });
+
+        let (nitro_timeline, nitro_gaps) = nitro_timeline(&nitro_payments);
+
+        let unconverted_currencies: BTreeSet<String> = converter
+            .map(|c| {
+                parsed
+                    .iter()
+                    .filter(|(.., payment)| payment.status == PaymentStatus::Completed)
+                    .filter(|(.., payment)| c.convert(&payment.currency, 0).is_none())
+                    .map(|(.., payment)| payment.currency.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        PaymentStats {
+            converted_currency: converter.map(|c| c.target().to_string()),
+            converted_total: totals.converted(converter),
+            unconverted_currencies: unconverted_currencies.into_iter().collect(),
+            totals: totals.into_vec(),
+            by_year: by_year
+                .into_iter()
+                .map(|(year, totals)| YearSpending {
+                    year,
+                    converted_total: totals.converted(converter),
+                    totals: totals.into_vec(),
+                })
+                .collect(),
+            by_product,
+            by_status: by_status
+                .into_iter()
+                .map(|(status, totals)| StatusSummary {
+                    status,
+                    converted_total: totals.converted(converter),
+                    totals: totals.into_vec(),
+                })
+                .collect(),
+            nitro_timeline,
+            nitro_gaps,
+            payments: parsed.into_iter().map(|(_, _, payment)| payment).collect(),
+        }
+    }
+}
+
+fn product_kind(description: &str) -> ProductKind {
+    let description = description.to_lowercase();
+    if description.contains("gift") {
+        ProductKind::Gift
+    } else if description.contains("boost") {
+        ProductKind::ServerBoost
+    } else if description.contains("basic") {
+        ProductKind::NitroBasic
+    } else if description.contains("classic") {
+        ProductKind::NitroClassic
+    } else if description.contains("nitro") {
+        ProductKind::Nitro
+    } else {
+        ProductKind::Other
+    }
+}
+
+/// Chains completed Nitro payments, sorted by date, into subscription periods. A payment
+/// extends the current period when it's for the same tier and lands before the period
+/// runs out; anything else starts a new period, with a gap if there's time in between.
+fn nitro_timeline(
+    payments: &[(DateTime<FixedOffset>, ProductKind, &String)],
+) -> (Vec<NitroPeriod>, Vec<NitroGap>) {
+    let mut periods: Vec<(
+        ProductKind,
+        DateTime<FixedOffset>,
+        DateTime<FixedOffset>,
+        u32,
+    )> = Vec::new();
+
+    for (paid_at, kind, description) in payments {
+        let months = if description.to_lowercase().contains("year") {
+            12
+        } else {
+            1
+        };
+        match periods.last_mut() {
+            Some((current_kind, _, end, count))
+                if current_kind == kind
+                    && *paid_at <= *end + chrono::Duration::days(NITRO_RENEWAL_GRACE_DAYS) =>
+            {
+                let from = (*end).max(*paid_at);
+                *end = from.checked_add_months(Months::new(months)).unwrap_or(from);
+                *count += 1;
+            }
+            _ => {
+                let end = paid_at
+                    .checked_add_months(Months::new(months))
+                    .unwrap_or(*paid_at);
+                periods.push((*kind, *paid_at, end, 1));
+            }
+        }
+    }
+
+    let gaps = periods
+        .windows(2)
+        .filter(|pair| pair[1].1 > pair[0].2)
+        .map(|pair| NitroGap {
+            start: pair[0].2.to_rfc3339(),
+            end: pair[1].1.to_rfc3339(),
+            days: (pair[1].1 - pair[0].2).num_days() as u32,
+        })
+        .collect();
+    let periods = periods
+        .into_iter()
+        .map(|(kind, start, end, payment_count)| NitroPeriod {
+            kind,
+            start: start.to_rfc3339(),
+            end: end.to_rfc3339(),
+            payment_count,
+        })
+        .collect();
+
+    (periods, gaps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ExtractionOptions;
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+
+    fn at(raw: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(raw).unwrap()
+    }
+
+    fn timeline(payments: &[(&str, ProductKind, &str)]) -> (Vec<NitroPeriod>, Vec<NitroGap>) {
+        let descriptions: Vec<String> = payments.iter().map(|p| p.2.to_string()).collect();
+        let payments: Vec<_> = payments
+            .iter()
+            .zip(&descriptions)
+            .map(|((paid_at, kind, _), description)| (at(paid_at), *kind, description))
+            .collect();
+        nitro_timeline(&payments)
+    }
+
+    #[test]
+    fn late_renewals_within_the_grace_period_extend_the_period() {
+        let (periods, gaps) = timeline(&[
+            (
+                "2021-01-05T10:00:00+00:00",
+                ProductKind::Nitro,
+                "Nitro Monthly",
+            ),
+            (
+                "2021-02-11T10:00:00+00:00",
+                ProductKind::Nitro,
+                "Nitro Monthly",
+            ),
+        ]);
+        assert_eq!(periods.len(), 1);
+        assert_eq!(periods[0].payment_count, 2);
+        assert_eq!(periods[0].start, "2021-01-05T10:00:00+00:00");
+        assert_eq!(periods[0].end, "2021-03-11T10:00:00+00:00");
+        assert!(gaps.is_empty());
+    }
+
+    #[test]
+    fn renewals_after_the_grace_period_leave_a_gap() {
+        let (periods, gaps) = timeline(&[
+            (
+                "2021-01-05T10:00:00+00:00",
+                ProductKind::Nitro,
+                "Nitro Monthly",
+            ),
+            (
+                "2021-02-13T10:00:00+00:00",
+                ProductKind::Nitro,
+                "Nitro Monthly",
+            ),
+        ]);
+        assert_eq!(periods.len(), 2);
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].start, "2021-02-05T10:00:00+00:00");
+        assert_eq!(gaps[0].end, "2021-02-13T10:00:00+00:00");
+        assert_eq!(gaps[0].days, 8);
+    }
+
+    #[test]
+    fn yearly_payments_cover_twelve_months() {
+        let (periods, _) = timeline(&[(
+            "2022-01-31T00:00:00+00:00",
+            ProductKind::Nitro,
+            "Nitro Yearly",
+        )]);
+        assert_eq!(periods[0].end, "2023-01-31T00:00:00+00:00");
+    }
+
+    #[test]
+    fn early_renewals_extend_from_the_end_of_the_period() {
+        let (periods, _) = timeline(&[
+            (
+                "2021-01-05T10:00:00+00:00",
+                ProductKind::Nitro,
+                "Nitro Monthly",
+            ),
+            (
+                "2021-01-20T10:00:00+00:00",
+                ProductKind::Nitro,
+                "Nitro Monthly",
+            ),
+        ]);
+        assert_eq!(periods.len(), 1);
+        assert_eq!(periods[0].end, "2021-03-05T10:00:00+00:00");
+    }
+
+    #[test]
+    fn tier_changes_start_a_new_period_without_a_gap() {
+        let (periods, gaps) = timeline(&[
+            (
+                "2021-01-05T10:00:00+00:00",
+                ProductKind::NitroBasic,
+                "Nitro Basic Monthly",
+            ),
+            (
+                "2021-02-05T10:00:00+00:00",
+                ProductKind::Nitro,
+                "Nitro Monthly",
+            ),
+        ]);
+        assert_eq!(periods.len(), 2);
+        assert_eq!(periods[0].kind, ProductKind::NitroBasic);
+        assert_eq!(periods[1].kind, ProductKind::Nitro);
+        assert!(gaps.is_empty());
+    }
+
+    #[test]
+    fn lists_undated_payments_last() {
+        let cancellation_token = Arc::new(AtomicBool::new(false));
+        let parser = Parser::new(&cancellation_token, Default::default());
+        let payment = |created_at: &str| DPayment {
+            status: 1,
+            currency: "usd".to_string(),
+            amount: 999,
+            created_at: created_at.to_string(),
+            description: "Nitro Monthly".to_string(),
+        };
+        let stats = parser.payment_stats(&[
+            payment("not a date"),
+            payment("2021-02-05T10:00:00+00:00"),
+            payment("2021-01-05T10:00:00+00:00"),
+        ]);
+        let dates: Vec<_> = stats
+            .payments
+            .iter()
+            .map(|p| p.created_at.as_deref())
+            .collect();
+        assert_eq!(
+            dates,
+            [
+                Some("2021-01-05T10:00:00+00:00"),
+                Some("2021-02-05T10:00:00+00:00"),
+                None
+            ]
+        );
+        assert_eq!(stats.totals[0].currency, "USD");
+        assert_eq!(stats.totals[0].amount, 2997);
+    }
+
+    #[test]
+    fn lists_unconverted_currencies_of_completed_payments_only() {
+        let cancellation_token = Arc::new(AtomicBool::new(false));
+        let parser = Parser::new(
+            &cancellation_token,
+            ExtractionOptions {
+                target_currency: Some("USD".to_string()),
+                ..Default::default()
+            },
+        );
+        let payment = |status: i32, currency: &str| DPayment {
+            status,
+            currency: currency.to_string(),
+            amount: 999,
+            created_at: "2021-01-05T10:00:00+00:00".to_string(),
+            description: "Nitro Monthly".to_string(),
+        };
+        let stats = parser.payment_stats(&[
+            payment(1, "xyz"),
+            payment(1, "usd"),
+            payment(2, "abc"),
+            payment(4, "def"),
+        ]);
+        assert_eq!(stats.unconverted_currencies, ["XYZ"]);
+    }
+
+    #[test]
+    fn classifies_products_from_descriptions() {
+        assert_eq!(product_kind("Nitro Monthly (Gift)"), ProductKind::Gift);
+        assert_eq!(
+            product_kind("Server Boost Monthly"),
+            ProductKind::ServerBoost
+        );
+        assert_eq!(product_kind("Nitro Basic Yearly"), ProductKind::NitroBasic);
+        assert_eq!(
+            product_kind("Nitro Classic Monthly"),
+            ProductKind::NitroClassic
+        );
+        assert_eq!(product_kind("Discord Nitro"), ProductKind::Nitro);
+        assert_eq!(product_kind("Some Game"), ProductKind::Other);
+    }
+}
@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+
+use crate::models::{ChannelPhrases, DChannel, Message, PhraseStats, TopChannel};
+use crate::parser::utils::top_counts;
+
+/// Counts 2- and 3-word phrases over the stop-word filtered words of each message.
+#[derive(Default)]
+pub(super) struct PhraseCollector {
+    bigrams: HashMap<String, u32>,
+    trigrams: HashMap<String, u32>,
+    channel_bigrams: HashMap<String, u32>,
+    channel_trigrams: HashMap<String, u32>,
+    /// Counts per channel, cut to the phrase limit as each channel ends.
+    by_channel: HashMap<String, PhraseCounts>,
+}
+
+#[derive(Default)]
+struct PhraseCounts {
+    bigrams: HashMap<String, u32>,
+    trigrams: HashMap<String, u32>,
+}
+
+impl PhraseCollector {
+    /// Phrases only join words that were adjacent in the message, never across a
+    /// `Message.phrase_breaks` entry.
+    pub(super) fn add(&mut self, message: &Message) {
+        let mut start = 0;
+        for end in message
+            .phrase_breaks
+            .iter()
+            .copied()
+            .chain([message.words.len()])
+        {
+            let run = &message.words[start..end.max(start)];
+            start = end;
+            for pair in run.windows(2) {
+                let phrase = pair.join(" ");
+                *self.channel_bigrams.entry(phrase.clone()).or_insert(0) += 1;
+                *self.bigrams.entry(phrase).or_insert(0) += 1;
+            }
+            for triple in run.windows(3) {
+                let phrase = triple.join(" ");
+                *self.channel_trigrams.entry(phrase.clone()).or_insert(0) += 1;
+                *self.trigrams.entry(phrase).or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// Files the channel's phrases under its own ID, or under its parent's when threads are
+    /// rolled up, mirroring how `top_channels` merges them. Only the top `limit` phrases are
+    /// kept, so a rolled-up phrase that misses the cut in every thread is dropped.
+    pub(super) fn end_channel(
+        &mut self,
+        channel: &DChannel,
+        roll_up_threads: bool,
+        limit: Option<u32>,
+    ) {
+        let bigrams = std::mem::take(&mut self.channel_bigrams);
+        let trigrams = std::mem::take(&mut self.channel_trigrams);
+        let id = match &channel.parent_id {
+            Some(parent_id) if roll_up_threads && channel.kind().is_thread() => parent_id,
+            _ => &channel.id,
+        };
+        let counts = self.by_channel.entry(id.clone()).or_default();
+        merge_top(&mut counts.bigrams, bigrams, limit);
+        merge_top(&mut counts.trigrams, trigrams, limit);
+    }
+
+    pub(super) fn finish(mut self, top_channels: &[TopChannel], limit: Option<u32>) -> PhraseStats {
+        PhraseStats {
+            by_channel: top_channels
+                .iter()
+                .filter_map(|channel| {
+                    let counts = self.by_channel.remove(&channel.id)?;
+                    Some(ChannelPhrases {
+                        channel_id: channel.id.clone(),
+                        channel_name: channel.name.clone(),
+                        top_bigrams: top_counts(counts.bigrams, limit),
+                        top_trigrams: top_counts(counts.trigrams, limit),
+                    })
+                })
+                .collect(),
+            top_bigrams: top_counts(self.bigrams, limit),
+            top_trigrams: top_counts(self.trigrams, limit),
+        }
+    }
+}
+
+fn merge_top(counts: &mut HashMap<String, u32>, other: HashMap<String, u32>, limit: Option<u32>) {
+    for (phrase, count) in other {
+        *counts.entry(phrase).or_insert(0) += count;
+    }
+    if limit.is_some_and(|limit| counts.len() > limit as usize) {
+        *counts = top_counts(std::mem::take(counts), limit)
+            .into_iter()
+            .map(|w| (w.word, w.count))
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ChannelKind;
+    use crate::parser::Parser;
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+
+    fn channel(id: &str, r#type: &str, parent_id: Option<&str>) -> DChannel {
+        DChannel {
+            id: id.to_string(),
+            name: Some(format!("channel-{}", id)),
+            r#type: Some(r#type.to_string()),
+            recipients: None,
+            guild: None,
+            parent_id: parent_id.map(str::to_string),
+        }
+    }
+
+    fn add_channel(collector: &mut PhraseCollector, channel: &DChannel, text: &str, roll_up: bool) {
+        collector.add(&Message {
+            id: 1,
+            timestamp: None,
+            timestamp_from_id: false,
+            content: text.to_string(),
+            attachments: Vec::new(),
+            links: Vec::new(),
+            length: text.chars().count() as u32,
+            words: text.split(' ').map(str::to_string).collect(),
+            phrase_breaks: Vec::new(),
+        });
+        collector.end_channel(channel, roll_up, None);
+    }
+
+    fn top_channel(id: &str, name: Option<&str>) -> TopChannel {
+        TopChannel {
+            id: id.to_string(),
+            name: name.map(str::to_string),
+            r#type: None,
+            kind: ChannelKind::Text,
+            message_count: 1,
+            guild_name: None,
+            guild_id: None,
+            created_at: None,
+            parent_id: None,
+            thread_count: 0,
+        }
+    }
+
+    fn bigrams(phrases: &ChannelPhrases) -> Vec<(&str, u32)> {
+        phrases
+            .top_bigrams
+            .iter()
+            .map(|w| (w.word.as_str(), w.count))
+            .collect()
+    }
+
+    #[test]
+    fn follows_top_channel_order() {
+        let mut collector = PhraseCollector::default();
+        add_channel(
+            &mut collector,
+            &channel("1", "0", None),
+            "good morning",
+            false,
+        );
+        add_channel(
+            &mut collector,
+            &channel("2", "0", None),
+            "good night",
+            false,
+        );
+        add_channel(&mut collector, &channel("3", "0", None), "not shown", false);
+
+        let stats = collector.finish(&[top_channel("2", Some("b")), top_channel("1", None)], None);
+        let ids: Vec<&str> = stats
+            .by_channel
+            .iter()
+            .map(|c| c.channel_id.as_str())
+            .collect();
+        assert_eq!(ids, ["2", "1"]);
+        assert_eq!(stats.by_channel[0].channel_name.as_deref(), Some("b"));
+        assert_eq!(bigrams(&stats.by_channel[0]), [("good night", 1)]);
+    }
+
+    #[test]
+    fn files_thread_phrases_under_the_parent_when_rolled_up() {
+        let mut collector = PhraseCollector::default();
+        add_channel(
+            &mut collector,
+            &channel("1", "0", None),
+            "good morning",
+            true,
+        );
+        add_channel(
+            &mut collector,
+            &channel("10", "11", Some("1")),
+            "good morning",
+            true,
+        );
+        add_channel(
+            &mut collector,
+            &channel("11", "11", Some("2")),
+            "hello there",
+            true,
+        );
+
+        let stats = collector.finish(&[top_channel("1", None), top_channel("2", None)], None);
+        assert_eq!(bigrams(&stats.by_channel[0]), [("good morning", 2)]);
+        assert_eq!(stats.by_channel[1].channel_id, "2");
+        assert_eq!(bigrams(&stats.by_channel[1]), [("hello there", 1)]);
+    }
+
+    #[test]
+    fn keeps_threads_separate_without_roll_up() {
+        let mut collector = PhraseCollector::default();
+        add_channel(
+            &mut collector,
+            &channel("1", "0", None),
+            "good morning",
+            false,
+        );
+        add_channel(
+            &mut collector,
+            &channel("10", "11", Some("1")),
+            "good morning",
+            false,
+        );
+
+        let stats = collector.finish(&[top_channel("1", None), top_channel("10", None)], None);
+        assert_eq!(bigrams(&stats.by_channel[0]), [("good morning", 1)]);
+        assert_eq!(bigrams(&stats.by_channel[1]), [("good morning", 1)]);
+    }
+
+    fn phrases_of(text: &str) -> (Vec<String>, Vec<String>) {
+        let cancellation_token = Arc::new(AtomicBool::new(false));
+        let parser = Parser::new(&cancellation_token, Default::default());
+        let (words, phrase_breaks) = parser.process_words(text);
+        let mut collector = PhraseCollector::default();
+        collector.add(&Message {
+            id: 1,
+            timestamp: None,
+            timestamp_from_id: false,
+            content: text.to_string(),
+            attachments: Vec::new(),
+            links: Vec::new(),
+            length: text.chars().count() as u32,
+            words,
+            phrase_breaks,
+        });
+        let stats = collector.finish(&[], None);
+        let mut bigrams: Vec<String> = stats.top_bigrams.into_iter().map(|w| w.word).collect();
+        let mut trigrams: Vec<String> = stats.top_trigrams.into_iter().map(|w| w.word).collect();
+        bigrams.sort();
+        trigrams.sort();
+        (bigrams, trigrams)
+    }
+
+    #[test]
+    fn never_joins_words_separated_by_a_dropped_token() {
+        let (bigrams, trigrams) = phrases_of("I went to the store");
+        assert!(bigrams.is_empty(), "{bigrams:?}");
+        assert!(trigrams.is_empty());
+
+        let (bigrams, trigrams) =
+            phrases_of("pizza party tonight 2 cheap pizza <@123> party https://x.com tonight");
+        assert_eq!(bigrams, ["cheap pizza", "party tonight", "pizza party"]);
+        assert_eq!(trigrams, ["pizza party tonight"]);
+    }
+
+    #[test]
+    fn cuts_channel_phrases_when_the_channel_ends() {
+        let mut collector = PhraseCollector::default();
+        collector.add(&Message {
+            id: 1,
+            timestamp: None,
+            timestamp_from_id: false,
+            content: String::new(),
+            attachments: Vec::new(),
+            links: Vec::new(),
+            length: 0,
+            words: "a b c a b d".split(' ').map(str::to_string).collect(),
+            phrase_breaks: Vec::new(),
+        });
+        collector.end_channel(&channel("1", "0", None), false, Some(1));
+
+        let counts = &collector.by_channel["1"];
+        assert_eq!(counts.bigrams, HashMap::from([("a b".to_string(), 2)]));
+        assert_eq!(counts.trigrams.len(), 1);
+
+        let stats = collector.finish(&[top_channel("1", None)], Some(1));
+        assert_eq!(bigrams(&stats.by_channel[0]), [("a b", 2)]);
+        assert_eq!(stats.top_trigrams.len(), 1);
+    }
+}
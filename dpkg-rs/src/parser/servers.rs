@@ -1,10 +1,11 @@
 use anyhow::Result;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::io::Read;
 use zip::ZipArchive;
 
 use crate::models::{DGuild, UserData};
 use crate::parser::{Callback, Parser};
+use crate::snowflake;
 
 impl<'a> Parser<'a> {
     pub(super) fn load_servers<R: Read + std::io::Seek>(
@@ -21,20 +22,72 @@ impl<'a> Parser<'a> {
 
         let guild_index_path = format!("{}/index.json", servers_root);
 
+        let mut guild_names: HashMap<String, String> = HashMap::new();
         if let Some(content) = self.read_file(archive, &guild_index_path)? {
             println!("[debug] Loading guild index from: {}", guild_index_path);
 
             match self.parse_json::<HashMap<String, String>>(&content) {
-                Ok(guilds_map) => {
-                    extracted_data.guilds = guilds_map
-                        .into_iter()
-                        .map(|(id, name)| DGuild { id, name })
-                        .collect()
-                }
+                Ok(guilds_map) => guild_names = guilds_map,
                 Err(e) => println!("[debug] Failed to parse guild index: {}", e),
             }
         }
 
+        // Guilds can have a guild.json without being listed in the index, and vice versa.
+        let guild_prefix = format!("{}/", servers_root);
+        let guild_ids: BTreeSet<String> = guild_names
+            .keys()
+            .cloned()
+            .chain(self.file_index.keys().filter_map(|path| {
+                let id = path
+                    .strip_prefix(&guild_prefix)?
+                    .strip_suffix("/guild.json")?;
+                (!id.contains('/')).then(|| id.to_string())
+            }))
+            .collect();
+
+        let user_id = extracted_data.user.as_ref().map(|u| u.id.clone());
+        let mut guilds = Vec::new();
+        let mut audit_logs = Vec::new();
+        for id in guild_ids {
+            self.check_cancellation_token()?;
+
+            let guild_path = format!("{}/{}/guild.json", servers_root, id);
+            let details = match self.read_file(archive, &guild_path)? {
+                Some(content) => match self.parse_json::<DGuild>(&content) {
+                    Ok(guild) => Some(guild),
+                    Err(e) => {
+                        println!("[debug] Failed to parse guild data for {}: {}", id, e);
+                        None
+                    }
+                },
+                None => None,
+            };
+
+            let mut guild = match (details, guild_names.remove(&id)) {
+                (Some(guild), _) => guild,
+                (None, Some(name)) => DGuild {
+                    id: id.clone(),
+                    name,
+                    ..Default::default()
+                },
+                (None, None) => continue,
+            };
+            guild.created_at = snowflake::created_at_str(&guild.id)
+                .map(|t| self.time_zone.localize(t).to_rfc3339());
+            guild.joined_at = guild
+                .joined_at
+                .as_deref()
+                .and_then(Parser::parse_timestamp)
+                .map(|t| self.time_zone.localize(t).to_rfc3339());
+            guild.is_owner = guild.owner_id.is_some() && guild.owner_id == user_id;
+            if let Some(audit_log) = self.load_audit_log(archive, servers_root, &guild)? {
+                audit_logs.push(audit_log);
+            }
+            guilds.push(guild);
+        }
+        extracted_data.guilds = guilds;
+        extracted_data.audit_logs = audit_logs;
+
         Ok(())
     }
 }
@@ -0,0 +1,290 @@
+/// Bundled stop-word lists, keyed by ISO 639-1 code. Words are lowercase.
+pub(super) const STOP_WORDS: [(&str, &[&str]); 10] = [
+    ("en", EN),
+    ("es", ES),
+    ("fr", FR),
+    ("de", DE),
+    ("pt", PT),
+    ("it", IT),
+    ("nl", NL),
+    ("pl", PL),
+    ("ru", RU),
+    ("tr", TR),
+];
+
+const EN: &[&str] = &[
+    "a",
+    "about",
+    "above",
+    "after",
+    "again",
+    "against",
+    "all",
+    "am",
+    "an",
+    "and",
+    "any",
+    "are",
+    "aren't",
+    "as",
+    "at",
+    "be",
+    "because",
+    "been",
+    "before",
+    "being",
+    "below",
+    "between",
+    "both",
+    "but",
+    "by",
+    "can",
+    "can't",
+    "cannot",
+    "could",
+    "couldn't",
+    "did",
+    "didn't",
+    "do",
+    "does",
+    "doesn't",
+    "doing",
+    "don't",
+    "down",
+    "during",
+    "each",
+    "few",
+    "for",
+    "from",
+    "further",
+    "had",
+    "hadn't",
+    "has",
+    "hasn't",
+    "have",
+    "haven't",
+    "having",
+    "he",
+    "he's",
+    "her",
+    "here",
+    "hers",
+    "herself",
+    "him",
+    "himself",
+    "his",
+    "how",
+    "i",
+    "i'd",
+    "i'll",
+    "i'm",
+    "i've",
+    "if",
+    "im",
+    "in",
+    "into",
+    "is",
+    "isn't",
+    "it",
+    "it's",
+    "its",
+    "itself",
+    "just",
+    "let's",
+    "me",
+    "more",
+    "most",
+    "my",
+    "myself",
+    "no",
+    "nor",
+    "not",
+    "now",
+    "of",
+    "off",
+    "on",
+    "once",
+    "only",
+    "or",
+    "other",
+    "our",
+    "ours",
+    "ourselves",
+    "out",
+    "over",
+    "own",
+    "same",
+    "she",
+    "she's",
+    "should",
+    "shouldn't",
+    "so",
+    "some",
+    "such",
+    "than",
+    "that",
+    "that's",
+    "the",
+    "their",
+    "theirs",
+    "them",
+    "themselves",
+    "then",
+    "there",
+    "there's",
+    "these",
+    "they",
+    "they're",
+    "this",
+    "those",
+    "through",
+    "to",
+    "too",
+    "under",
+    "until",
+    "up",
+    "very",
+    "was",
+    "wasn't",
+    "we",
+    "we're",
+    "were",
+    "weren't",
+    "what",
+    "what's",
+    "when",
+    "where",
+    "which",
+    "while",
+    "who",
+    "whom",
+    "why",
+    "will",
+    "with",
+    "won't",
+    "would",
+    "wouldn't",
+    "you",
+    "you're",
+    "your",
+    "yours",
+    "yourself",
+    "yourselves",
+];
+
+const ES: &[&str] = &[
+    "a", "al", "algo", "algunos", "ante", "antes", "como", "con", "contra", "cual", "cuando", "de",
+    "del", "desde", "donde", "durante", "e", "el", "ella", "ellas", "ellos", "en", "entre", "era",
+    "es", "esa", "esas", "ese", "eso", "esos", "esta", "estaba", "estas", "este", "esto", "estos",
+    "está", "están", "fue", "ha", "hay", "la", "las", "le", "les", "lo", "los", "me", "mi", "mis",
+    "muy", "más", "mí", "nada", "ni", "no", "nos", "nosotros", "o", "os", "otra", "otro", "para",
+    "pero", "poco", "por", "porque", "que", "quien", "qué", "se", "sea", "ser", "si", "sin",
+    "sobre", "su", "sus", "sí", "también", "te", "tengo", "ti", "tiene", "todo", "todos", "tu",
+    "tus", "tú", "un", "una", "uno", "unos", "y", "ya", "yo", "él",
+];
+
+const FR: &[&str] = &[
+    "a", "ai", "au", "aux", "avec", "c'est", "ce", "ces", "cette", "dans", "de", "des", "du",
+    "elle", "elles", "en", "est", "et", "eu", "il", "ils", "j'ai", "je", "la", "le", "les", "leur",
+    "leurs", "lui", "ma", "mais", "me", "mes", "moi", "mon", "même", "ne", "nos", "notre", "nous",
+    "on", "ou", "où", "par", "pas", "pour", "qu", "que", "qui", "sa", "se", "ses", "son", "sont",
+    "sur", "ta", "te", "tes", "toi", "ton", "tu", "un", "une", "vos", "votre", "vous", "y", "à",
+    "ça", "été", "être",
+];
+
+const DE: &[&str] = &[
+    "aber", "alle", "als", "also", "am", "an", "auch", "auf", "aus", "bei", "bin", "bis", "bist",
+    "da", "das", "dass", "dein", "dem", "den", "der", "des", "dich", "die", "dir", "doch", "du",
+    "ein", "eine", "einem", "einen", "einer", "es", "für", "hab", "habe", "hat", "ich", "ihr",
+    "im", "in", "ist", "ja", "jetzt", "kann", "kein", "mal", "man", "mein", "mich", "mir", "mit",
+    "nach", "nicht", "noch", "nur", "oder", "schon", "sein", "sich", "sie", "sind", "so", "und",
+    "uns", "von", "vor", "war", "was", "wenn", "wie", "wir", "wird", "zu", "zum", "zur", "über",
+];
+
+const PT: &[&str] = &[
+    "a", "ao", "aos", "as", "com", "como", "da", "das", "de", "dela", "dele", "do", "dos", "e",
+    "ela", "ele", "eles", "em", "entre", "era", "essa", "esse", "esta", "este", "eu", "foi", "há",
+    "isso", "isto", "já", "lhe", "mais", "mas", "me", "meu", "minha", "muito", "na", "nas", "no",
+    "nos", "não", "o", "os", "ou", "para", "pela", "pelo", "por", "quando", "que", "se", "sem",
+    "seu", "sua", "só", "também", "te", "tem", "um", "uma", "você", "à", "é",
+];
+
+const IT: &[&str] = &[
+    "a", "ai", "al", "alla", "anche", "che", "chi", "ci", "come", "con", "da", "dal", "dei", "del",
+    "della", "di", "e", "era", "gli", "ha", "ho", "i", "il", "in", "io", "la", "le", "lei", "lo",
+    "lui", "ma", "mi", "mio", "ne", "nel", "nella", "no", "noi", "non", "o", "per", "perché",
+    "più", "quando", "quello", "questo", "se", "si", "sono", "su", "sua", "suo", "ti", "tu",
+    "tutto", "un", "una", "uno", "è",
+];
+
+const NL: &[&str] = &[
+    "aan", "al", "als", "ben", "bij", "dan", "dat", "de", "die", "dit", "door", "een", "en", "er",
+    "geen", "had", "heb", "heeft", "het", "hij", "hoe", "hun", "ik", "in", "is", "je", "jij",
+    "kan", "maar", "me", "met", "mij", "mijn", "na", "naar", "niet", "nog", "nu", "of", "om",
+    "ook", "op", "over", "te", "tot", "uit", "van", "voor", "was", "wat", "we", "wel", "wie",
+    "wij", "zijn", "zo", "ze", "zich",
+];
+
+const PL: &[&str] = &[
+    "a", "ale", "bo", "by", "być", "co", "czy", "dla", "do", "go", "i", "ich", "jak", "jest",
+    "jestem", "jego", "jej", "już", "ja", "mi", "mnie", "na", "nie", "no", "o", "od", "po", "się",
+    "ta", "tak", "tam", "te", "tego", "tej", "to", "ty", "tylko", "w", "we", "z", "za", "że",
+    "żeby",
+];
+
+const RU: &[&str] = &[
+    "а",
+    "бы",
+    "в",
+    "вот",
+    "все",
+    "всё",
+    "вы",
+    "да",
+    "для",
+    "до",
+    "его",
+    "если",
+    "есть",
+    "же",
+    "за",
+    "и",
+    "из",
+    "или",
+    "их",
+    "к",
+    "как",
+    "когда",
+    "кто",
+    "ли",
+    "мне",
+    "мы",
+    "на",
+    "не",
+    "нет",
+    "но",
+    "ну",
+    "о",
+    "он",
+    "она",
+    "они",
+    "от",
+    "по",
+    "с",
+    "так",
+    "там",
+    "то",
+    "тоже",
+    "ты",
+    "у",
+    "уже",
+    "что",
+    "это",
+    "я",
+];
+
+const TR: &[&str] = &[
+    "ama", "bir", "biz", "bu", "da", "de", "daha", "diye", "en", "gibi", "için", "ile", "ise",
+    "kadar", "ki", "mi", "mı", "ne", "o", "onu", "sen", "siz", "şey", "şu", "ve", "ya", "yani",
+    "çok", "ben",
+];
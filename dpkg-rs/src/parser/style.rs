@@ -0,0 +1,259 @@
+use chrono::{DateTime, FixedOffset};
+use std::collections::BTreeMap;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::models::{DChannel, LengthBucket, LongestMessage, Message, MessageStyleStats};
+use crate::parser::emojis::{CUSTOM_EMOTE_REGEX, is_emoji_grapheme};
+use crate::parser::links::LINK_REGEX;
+
+/// Lower bounds of the length histogram buckets, in characters.
+const LENGTH_BUCKETS: [u32; 9] = [1, 11, 26, 51, 101, 251, 501, 1001, 2001];
+
+#[derive(Default)]
+pub(super) struct StyleCollector {
+    lengths: BTreeMap<u32, u32>,
+    longest_message: Option<LongestMessage>,
+    links_only_count: u32,
+    emoji_only_count: u32,
+    attachments_only_count: u32,
+    code_block_count: u32,
+    inline_code_count: u32,
+    spoiler_count: u32,
+    quote_count: u32,
+}
+
+impl StyleCollector {
+    pub(super) fn add(
+        &mut self,
+        channel: &DChannel,
+        message: &Message,
+        local_time: Option<DateTime<FixedOffset>>,
+    ) {
+        let content = message.content.trim();
+        if content.is_empty() {
+            if !message.attachments.is_empty() {
+                self.attachments_only_count += 1;
+            }
+            return;
+        }
+
+        *self.lengths.entry(message.length).or_insert(0) += 1;
+        if self
+            .longest_message
+            .as_ref()
+            .is_none_or(|longest| message.length > longest.length)
+        {
+            self.longest_message = Some(LongestMessage {
+                message_id: message.id.to_string(),
+                channel_id: channel.id.clone(),
+                channel_name: channel.name.clone(),
+                length: message.length,
+                timestamp: local_time.map(|t| t.to_rfc3339()),
+            });
+        }
+
+        if !message.links.is_empty() && LINK_REGEX.replace_all(content, "").trim().is_empty() {
+            self.links_only_count += 1;
+        } else if is_emoji_only(content) {
+            self.emoji_only_count += 1;
+        }
+
+        if content.contains("```") {
+            self.code_block_count += 1;
+        } else if content.matches('`').count() >= 2 {
+            self.inline_code_count += 1;
+        }
+        if content.matches("||").count() >= 2 {
+            self.spoiler_count += 1;
+        }
+        if content
+            .lines()
+            .any(|line| line.starts_with("> ") || line.starts_with(">>> "))
+        {
+            self.quote_count += 1;
+        }
+    }
+
+    pub(super) fn finish(self) -> MessageStyleStats {
+        let message_count: u32 = self.lengths.values().sum();
+        let total_length: u64 = self
+            .lengths
+            .iter()
+            .map(|(length, count)| *length as u64 * *count as u64)
+            .sum();
+
+        let mut seen = 0;
+        let median_length = self
+            .lengths
+            .iter()
+            .find(|(_, count)| {
+                seen += **count;
+                seen * 2 >= message_count
+            })
+            .map(|(length, _)| *length)
+            .unwrap_or(0);
+
+        let length_histogram = LENGTH_BUCKETS
+            .iter()
+            .enumerate()
+            .map(|(i, &min)| {
+                let max = LENGTH_BUCKETS.get(i + 1).map(|next| next - 1);
+                LengthBucket {
+                    min,
+                    max,
+                    count: self
+                        .lengths
+                        .range(min..=max.unwrap_or(u32::MAX))
+                        .map(|(_, count)| count)
+                        .sum(),
+                }
+            })
+            .collect();
+
+        MessageStyleStats {
+            length_histogram,
+            average_length: if message_count > 0 {
+                total_length as f64 / message_count as f64
+            } else {
+                0.0
+            },
+            median_length,
+            longest_message: self.longest_message,
+            links_only_count: self.links_only_count,
+            emoji_only_count: self.emoji_only_count,
+            attachments_only_count: self.attachments_only_count,
+            code_block_count: self.code_block_count,
+            inline_code_count: self.inline_code_count,
+            spoiler_count: self.spoiler_count,
+            quote_count: self.quote_count,
+        }
+    }
+}
+
+/// Whether a message is made only of custom emotes and Unicode emoji.
+fn is_emoji_only(content: &str) -> bool {
+    let has_custom = CUSTOM_EMOTE_REGEX.is_match(content);
+    let rest = CUSTOM_EMOTE_REGEX.replace_all(content, " ");
+    let mut has_unicode = false;
+    for grapheme in rest.graphemes(true) {
+        if grapheme.trim().is_empty() {
+            continue;
+        }
+        if !is_emoji_grapheme(grapheme) {
+            return false;
+        }
+        has_unicode = true;
+    }
+    has_custom || has_unicode
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn style(messages: &[(&str, &[&str])]) -> MessageStyleStats {
+        let channel = DChannel {
+            id: "1".to_string(),
+            name: None,
+            r#type: None,
+            recipients: None,
+            guild: None,
+            parent_id: None,
+        };
+        let mut collector = StyleCollector::default();
+        for (id, (content, attachments)) in messages.iter().enumerate() {
+            collector.add(
+                &channel,
+                &Message {
+                    id: id as u64,
+                    timestamp: None,
+                    timestamp_from_id: false,
+                    content: content.to_string(),
+                    attachments: attachments.iter().map(|a| a.to_string()).collect(),
+                    links: crate::parser::Parser::process_links(content),
+                    length: content.chars().count() as u32,
+                    words: Vec::new(),
+                    phrase_breaks: Vec::new(),
+                },
+                None,
+            );
+        }
+        collector.finish()
+    }
+
+    fn texts(contents: &[&str]) -> MessageStyleStats {
+        let messages: Vec<(&str, &[&str])> = contents.iter().map(|c| (*c, &[][..])).collect();
+        style(&messages)
+    }
+
+    #[test]
+    fn takes_the_middle_length_for_odd_counts() {
+        let stats = texts(&["a", "abcde", "abc"]);
+        assert_eq!(stats.median_length, 3);
+        assert_eq!(stats.average_length, 3.0);
+        assert_eq!(stats.longest_message.unwrap().message_id, "1");
+    }
+
+    #[test]
+    fn takes_the_lower_middle_length_for_even_counts() {
+        assert_eq!(
+            texts(&["ab", "abcd", "abcdef", "abcdefgh"]).median_length,
+            4
+        );
+        assert_eq!(texts(&[]).median_length, 0);
+    }
+
+    #[test]
+    fn fills_histogram_buckets_inclusively() {
+        let stats = texts(&[
+            &"x".repeat(10),
+            &"x".repeat(11),
+            &"x".repeat(25),
+            &"x".repeat(2000),
+            &"x".repeat(2001),
+            &"x".repeat(5000),
+        ]);
+        let buckets: Vec<(u32, Option<u32>, u32)> = stats
+            .length_histogram
+            .iter()
+            .map(|b| (b.min, b.max, b.count))
+            .collect();
+        assert_eq!(buckets[0], (1, Some(10), 1));
+        assert_eq!(buckets[1], (11, Some(25), 2));
+        assert_eq!(buckets[7], (1001, Some(2000), 1));
+        assert_eq!(buckets[8], (2001, None, 2));
+    }
+
+    #[test]
+    fn classifies_link_emoji_and_attachment_only_messages() {
+        let stats = style(&[
+            ("https://example.com  https://x.org", &[]),
+            ("look https://example.com", &[]),
+            ("<:wave:123> \u{1F600}", &[]),
+            ("\u{1F600} ok", &[]),
+            ("\u{2714}", &[]),
+            ("", &["https://cdn/a.png"]),
+            ("", &[]),
+        ]);
+        assert_eq!(stats.links_only_count, 1);
+        assert_eq!(stats.emoji_only_count, 1);
+        assert_eq!(stats.attachments_only_count, 1);
+    }
+
+    #[test]
+    fn detects_markdown() {
+        let stats = texts(&[
+            "```rust\nfn main() {}\n```",
+            "use `cargo test` here",
+            "a single ` backtick",
+            "||spoiler|| text",
+            "> quoted\nreply",
+            "text\n>>> long quote",
+            ">not a quote",
+        ]);
+        assert_eq!(stats.code_block_count, 1);
+        assert_eq!(stats.inline_code_count, 1);
+        assert_eq!(stats.spoiler_count, 1);
+        assert_eq!(stats.quote_count, 2);
+    }
+}
@@ -0,0 +1,242 @@
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset, Offset, Utc};
+use chrono_tz::Tz;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Seek};
+use zip::ZipArchive;
+
+use crate::models::{ClientTimeZoneEvent, TimeZoneSetting};
+use crate::parser::{Callback, Parser, Step};
+
+/// How many analytics events carrying `client_timezone` are sampled when inferring the zone.
+const TIME_ZONE_SAMPLE_SIZE: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LocalTimeZone {
+    Utc,
+    Named(Tz),
+    Fixed(FixedOffset),
+}
+
+impl LocalTimeZone {
+    pub fn localize(&self, timestamp: DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self {
+            LocalTimeZone::Utc => timestamp.fixed_offset(),
+            LocalTimeZone::Named(tz) => {
+                let local = timestamp.with_timezone(tz);
+                local.with_timezone(&local.offset().fix())
+            }
+            LocalTimeZone::Fixed(offset) => timestamp.with_timezone(offset),
+        }
+    }
+
+    fn from_offset_minutes(minutes: i64) -> Option<Self> {
+        i32::try_from(minutes * 60)
+            .ok()
+            .and_then(FixedOffset::east_opt)
+            .map(LocalTimeZone::Fixed)
+    }
+
+    /// Accepts an IANA name, an offset like `+05:30`, or a number of minutes as returned by
+    /// JavaScript's `getTimezoneOffset()`, which counts minutes *west* of UTC.
+    fn from_client_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::String(name) => match name.parse::<Tz>() {
+                Ok(tz) => Some(LocalTimeZone::Named(tz)),
+                Err(_) => name
+                    .trim()
+                    .parse::<FixedOffset>()
+                    .ok()
+                    .map(LocalTimeZone::Fixed),
+            },
+            Value::Number(minutes) => minutes
+                .as_i64()
+                .and_then(|minutes| Self::from_offset_minutes(-minutes)),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            LocalTimeZone::Utc => "UTC".to_string(),
+            LocalTimeZone::Named(tz) => tz.name().to_string(),
+            LocalTimeZone::Fixed(offset) => offset.to_string(),
+        }
+    }
+}
+
+impl<'a> Parser<'a> {
+    pub(super) fn resolve_time_zone<R: Read + Seek>(
+        &self,
+        archive: &mut ZipArchive<R>,
+        callback: &Callback,
+    ) -> Result<LocalTimeZone> {
+        let time_zone = match &self.options.time_zone {
+            None => LocalTimeZone::Utc,
+            Some(TimeZoneSetting::Iana { name }) => match name.parse::<Tz>() {
+                Ok(tz) => LocalTimeZone::Named(tz),
+                Err(_) => {
+                    callback.progress(
+                        Step::Messages,
+                        format!("Unknown time zone \"{}\", falling back to UTC", name),
+                    );
+                    LocalTimeZone::Utc
+                }
+            },
+            Some(TimeZoneSetting::FixedOffset { offset_minutes }) => {
+                match LocalTimeZone::from_offset_minutes(*offset_minutes as i64) {
+                    Some(tz) => tz,
+                    None => {
+                        callback.progress(
+                            Step::Messages,
+                            format!(
+                                "Invalid UTC offset of {} minutes, falling back to UTC",
+                                offset_minutes
+                            ),
+                        );
+                        LocalTimeZone::Utc
+                    }
+                }
+            }
+            Some(TimeZoneSetting::InferFromAnalytics) => {
+                callback.progress(
+                    Step::Messages,
+                    "Detecting time zone from analytics...".to_string(),
+                );
+                match self.infer_time_zone(archive)? {
+                    Some(tz) => tz,
+                    None => {
+                        callback.progress(
+                            Step::Messages,
+                            "Could not detect time zone from analytics, using UTC".to_string(),
+                        );
+                        LocalTimeZone::Utc
+                    }
+                }
+            }
+        };
+
+        println!("[debug] Using time zone: {:?}", time_zone);
+        Ok(time_zone)
+    }
+
+    fn infer_time_zone<R: Read + Seek>(
+        &self,
+        archive: &mut ZipArchive<R>,
+    ) -> Result<Option<LocalTimeZone>> {
+        let file_names: Vec<&String> = self.file_index.keys().collect();
+        let Ok(analytics_file_name) = Parser::get_analytics_root(&file_names) else {
+            return Ok(None);
+        };
+
+        let file = archive.by_name(&analytics_file_name)?;
+        let reader = BufReader::new(file);
+
+        let mut seen: HashMap<LocalTimeZone, u32> = HashMap::new();
+        let mut sampled = 0;
+
+        for line in reader.lines() {
+            self.check_cancellation_token()?;
+            let Ok(line) = line else {
+                continue;
+            };
+            if !line.contains("\"client_timezone\"") {
+                continue;
+            }
+
+            let mut bytes = line.into_bytes();
+            if let Ok(ClientTimeZoneEvent {
+                client_timezone: Some(value),
+            }) = simd_json::from_slice::<ClientTimeZoneEvent>(&mut bytes)
+                && let Some(time_zone) = LocalTimeZone::from_client_value(&value)
+            {
+                *seen.entry(time_zone).or_insert(0) += 1;
+                sampled += 1;
+                if sampled >= TIME_ZONE_SAMPLE_SIZE {
+                    break;
+                }
+            }
+        }
+
+        Ok(most_common(seen))
+    }
+}
+
+/// Most sampled zone; ties go to the alphabetically first name so every run agrees.
+fn most_common(seen: HashMap<LocalTimeZone, u32>) -> Option<LocalTimeZone> {
+    seen.into_iter()
+        .max_by(|(a, a_count), (b, b_count)| {
+            a_count.cmp(b_count).then_with(|| b.name().cmp(&a.name()))
+        })
+        .map(|(time_zone, _)| time_zone)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+    use serde_json::json;
+
+    fn hour_at_noon_utc(time_zone: LocalTimeZone) -> u32 {
+        let noon = DateTime::parse_from_rfc3339("2024-01-15T12:00:00Z").unwrap();
+        time_zone.localize(noon.to_utc()).hour()
+    }
+
+    #[test]
+    fn reads_named_client_time_zones() {
+        let time_zone = LocalTimeZone::from_client_value(&json!("America/Los_Angeles")).unwrap();
+        assert_eq!(
+            time_zone,
+            LocalTimeZone::Named(chrono_tz::America::Los_Angeles)
+        );
+        assert_eq!(hour_at_noon_utc(time_zone), 4);
+    }
+
+    #[test]
+    fn reads_offset_client_time_zones() {
+        let time_zone = LocalTimeZone::from_client_value(&json!("+05:30")).unwrap();
+        assert_eq!(time_zone.name(), "+05:30");
+        assert_eq!(hour_at_noon_utc(time_zone), 17);
+    }
+
+    #[test]
+    fn treats_numeric_client_time_zones_as_minutes_west_of_utc() {
+        // getTimezoneOffset() is 480 in UTC-8 and -60 in UTC+1.
+        let west = LocalTimeZone::from_client_value(&json!(480)).unwrap();
+        assert_eq!(hour_at_noon_utc(west), 4);
+        let east = LocalTimeZone::from_client_value(&json!(-60)).unwrap();
+        assert_eq!(hour_at_noon_utc(east), 13);
+    }
+
+    #[test]
+    fn treats_option_offsets_as_minutes_east_of_utc() {
+        let time_zone = LocalTimeZone::from_offset_minutes(-480).unwrap();
+        assert_eq!(hour_at_noon_utc(time_zone), 4);
+    }
+
+    #[test]
+    fn rejects_unknown_client_time_zones() {
+        assert_eq!(
+            LocalTimeZone::from_client_value(&json!("Mars/Olympus")),
+            None
+        );
+        assert_eq!(LocalTimeZone::from_client_value(&json!(true)), None);
+    }
+
+    #[test]
+    fn breaks_ties_by_name() {
+        for _ in 0..20 {
+            let seen = HashMap::from([
+                (LocalTimeZone::Named(chrono_tz::Europe::Paris), 3),
+                (LocalTimeZone::Named(chrono_tz::Europe::Berlin), 3),
+                (LocalTimeZone::Utc, 1),
+            ]);
+            assert_eq!(
+                most_common(seen),
+                Some(LocalTimeZone::Named(chrono_tz::Europe::Berlin))
+            );
+        }
+        assert_eq!(most_common(HashMap::new()), None);
+    }
+}
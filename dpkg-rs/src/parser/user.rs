@@ -2,7 +2,7 @@ use anyhow::Result;
 use std::io::Read;
 use zip::ZipArchive;
 
-use crate::models::{DUser, UserData};
+use crate::models::{DRelationship, DUser, RelationshipKind, RelationshipSummary, UserData};
 use crate::parser::{Callback, Parser};
 
 impl<'a> Parser<'a> {
@@ -23,13 +23,37 @@ impl<'a> Parser<'a> {
         if let Some(content) = self.read_file(archive, &user_path)? {
             println!("[debug] Loading user info from: {}", user_path);
 
-            if let Ok(user) = self.parse_json::<DUser>(&content) {
-                extracted_data.user = Some(user);
-            } else {
-                println!("[debug] Failed to parse user.json");
+            match self.parse_json::<DUser>(&content) {
+                Ok(user) => {
+                    extracted_data.relationships = summarize_relationships(&user.relationships);
+                    extracted_data.payments = self.payment_stats(&user.payments);
+                    extracted_data.activities =
+                        self.activity_stats(&user.user_activity_application_statistics);
+                    extracted_data.user = Some(user);
+                }
+                Err(e) => println!("[debug] Failed to parse user.json: {}", e),
             }
         }
 
         Ok(())
     }
 }
+
+fn summarize_relationships(relationships: &[DRelationship]) -> RelationshipSummary {
+    let mut summary = RelationshipSummary::default();
+    for relationship in relationships {
+        let count = match relationship.r#type {
+            RelationshipKind::Friend => &mut summary.friend_count,
+            RelationshipKind::Blocked => &mut summary.blocked_count,
+            RelationshipKind::IncomingRequest => &mut summary.incoming_request_count,
+            RelationshipKind::OutgoingRequest => &mut summary.outgoing_request_count,
+            RelationshipKind::Implicit => &mut summary.implicit_count,
+            RelationshipKind::Unknown => &mut summary.unknown_count,
+        };
+        *count += 1;
+        if relationship.nickname.is_some() {
+            summary.nicknamed_count += 1;
+        }
+    }
+    summary
+}
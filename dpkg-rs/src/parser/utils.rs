@@ -1,10 +1,25 @@
 use anyhow::{Result, anyhow};
+use chrono::{DateTime, NaiveDateTime, Utc};
 use regex::Regex;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::io::{BufReader, Read};
 use zip::ZipArchive;
 
+use crate::models::WordCount;
 use crate::parser::Parser;
+use crate::snowflake;
+
+/// Timestamp formats carrying a UTC offset (`+00:00`, `+0000` or `Z`), as seen in
+/// old CSV exports and newer `messages.json` files.
+const OFFSET_TIMESTAMP_FORMATS: [&str; 3] = [
+    "%Y-%m-%d %H:%M:%S%.f%#z",
+    "%Y-%m-%dT%H:%M:%S%.f%#z",
+    "%Y-%m-%d %H:%M:%S%.f %#z",
+];
+
+/// Timestamp formats without an offset, which Discord writes in UTC.
+const NAIVE_TIMESTAMP_FORMATS: [&str; 2] = ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"];
 
 impl<'a> Parser<'a> {
     pub(crate) fn read_file<R: Read + std::io::Seek>(
@@ -59,8 +74,38 @@ impl<'a> Parser<'a> {
         self.file_index.contains_key(path)
     }
 
-    pub(crate) fn process_words(content: &str) -> Vec<String> {
-        content.split_whitespace().map(|s| s.to_string()).collect()
+    pub(crate) fn parse_timestamp(raw: &str) -> Option<DateTime<Utc>> {
+        let raw = raw.trim();
+        if raw.is_empty() {
+            return None;
+        }
+        OFFSET_TIMESTAMP_FORMATS
+            .iter()
+            .find_map(|format| DateTime::parse_from_str(raw, format).ok())
+            .map(|dt| dt.with_timezone(&Utc))
+            .or_else(|| {
+                NAIVE_TIMESTAMP_FORMATS
+                    .iter()
+                    .find_map(|format| NaiveDateTime::parse_from_str(raw, format).ok())
+                    .map(|dt| dt.and_utc())
+            })
+    }
+
+    /// Parses a message's `Timestamp`, falling back to the creation time encoded in its ID
+    /// when the field is missing or unparsable. The flag tells whether the fallback produced
+    /// the timestamp.
+    pub(crate) fn message_timestamp(id: u64, raw: Option<&str>) -> (Option<DateTime<Utc>>, bool) {
+        match raw.and_then(Parser::parse_timestamp) {
+            Some(timestamp) => (Some(timestamp), false),
+            None => {
+                let fallback = snowflake::created_at(id);
+                (fallback, fallback.is_some())
+            }
+        }
+    }
+
+    pub(crate) fn process_attachments(raw: &str) -> Vec<String> {
+        raw.split_whitespace().map(|s| s.to_string()).collect()
     }
 
     pub(crate) fn get_messages_root(files: &[&String]) -> Result<String> {
@@ -102,3 +147,124 @@ impl<'a> Parser<'a> {
         Ok(sample.to_string())
     }
 }
+
+/// Converts an optional ranking limit into a `take` count, `None` meaning unlimited.
+pub(crate) fn limit_or_all(limit: Option<u32>) -> usize {
+    limit.map_or(usize::MAX, |limit| limit as usize)
+}
+
+/// Sorts `counts` by descending count, ties alphabetically, and keeps the first `limit`
+/// entries. The tie-break keeps rankings built from a `HashMap` stable between runs.
+pub(crate) fn top_counts(counts: HashMap<String, u32>, limit: Option<u32>) -> Vec<WordCount> {
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+        .into_iter()
+        .take(limit_or_all(limit))
+        .map(|(word, count)| WordCount { word, count })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(raw: &str) -> Option<DateTime<Utc>> {
+        Some(
+            DateTime::parse_from_rfc3339(raw)
+                .unwrap()
+                .with_timezone(&Utc),
+        )
+    }
+
+    #[test]
+    fn parses_offset_formats() {
+        let expected = utc("2021-03-04T05:06:07.123Z");
+        assert_eq!(
+            Parser::parse_timestamp("2021-03-04 05:06:07.123+00:00"),
+            expected
+        );
+        assert_eq!(
+            Parser::parse_timestamp("2021-03-04T05:06:07.123+00:00"),
+            expected
+        );
+        assert_eq!(
+            Parser::parse_timestamp("2021-03-04T05:06:07.123Z"),
+            expected
+        );
+        assert_eq!(
+            Parser::parse_timestamp("2021-03-04 05:06:07.123 +0000"),
+            expected
+        );
+    }
+
+    #[test]
+    fn converts_offsets_to_utc() {
+        assert_eq!(
+            Parser::parse_timestamp("2021-03-04T07:06:07+02:00"),
+            utc("2021-03-04T05:06:07Z")
+        );
+    }
+
+    #[test]
+    fn treats_naive_timestamps_as_utc() {
+        let expected = utc("2021-03-04T05:06:07Z");
+        assert_eq!(Parser::parse_timestamp("2021-03-04 05:06:07"), expected);
+        assert_eq!(Parser::parse_timestamp("2021-03-04T05:06:07"), expected);
+        assert_eq!(
+            Parser::parse_timestamp(" 2021-03-04 05:06:07.000000 "),
+            expected
+        );
+    }
+
+    #[test]
+    fn falls_back_to_the_message_id() {
+        let id = 175928847299117063;
+        assert_eq!(
+            Parser::message_timestamp(id, Some("2021-03-04 05:06:07")),
+            (utc("2021-03-04T05:06:07Z"), false)
+        );
+        assert_eq!(
+            Parser::message_timestamp(id, Some("garbage")),
+            (utc("2016-04-30T11:18:25.796Z"), true)
+        );
+        assert_eq!(
+            Parser::message_timestamp(id, None),
+            (utc("2016-04-30T11:18:25.796Z"), true)
+        );
+    }
+
+    #[test]
+    fn reports_no_fallback_when_the_id_has_no_timestamp() {
+        assert_eq!(Parser::message_timestamp(0, Some("garbage")), (None, false));
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert_eq!(Parser::parse_timestamp(""), None);
+        assert_eq!(Parser::parse_timestamp("yesterday"), None);
+        assert_eq!(Parser::parse_timestamp("2021-13-04 05:06:07"), None);
+    }
+
+    #[test]
+    fn breaks_ties_alphabetically_before_truncating() {
+        let counts = HashMap::from([
+            ("pear".to_string(), 2),
+            ("apple".to_string(), 2),
+            ("fig".to_string(), 5),
+            ("kiwi".to_string(), 2),
+        ]);
+        let top: Vec<(String, u32)> = top_counts(counts, Some(3))
+            .into_iter()
+            .map(|c| (c.word, c.count))
+            .collect();
+        assert_eq!(
+            top,
+            [
+                ("fig".to_string(), 5),
+                ("apple".to_string(), 2),
+                ("kiwi".to_string(), 2)
+            ]
+        );
+    }
+}
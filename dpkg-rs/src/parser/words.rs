@@ -0,0 +1,159 @@
+use std::collections::HashSet;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::models::ExtractionOptions;
+use crate::parser::Parser;
+use crate::parser::emojis::CUSTOM_EMOTE_REGEX;
+use crate::parser::links::LINK_REGEX;
+use crate::parser::mentions::MENTION_REGEX;
+use crate::parser::stop_words::STOP_WORDS;
+
+/// Stop-word list applied when `ExtractionOptions.stop_word_languages` is `None`.
+const DEFAULT_STOP_WORD_LANGUAGE: &str = "en";
+
+/// CJK words carry far more meaning per character, so two characters are enough to count.
+const MIN_CJK_WORD_LENGTH: usize = 2;
+
+impl<'a> Parser<'a> {
+    /// Splits message contents into lowercase words on Unicode word boundaries, dropping
+    /// punctuation, numbers, links, mentions, custom emotes and stop words. Runs of Han
+    /// and Hiragana characters, which have no spaces to split on, become character bigrams.
+    /// Also returns the indices into the words where dropped tokens were, see
+    /// `Message.phrase_breaks`.
+    pub(crate) fn process_words(&self, content: &str) -> (Vec<String>, Vec<usize>) {
+        // Markup becomes a number, which is dropped like any other and so breaks phrases.
+        let content = replace_markup(content, " 0 ");
+
+        let mut words = Vec::new();
+        let mut breaks: Vec<usize> = Vec::new();
+        let mut ideographs: Vec<char> = Vec::new();
+        let mut ideographs_end = 0;
+
+        for (start, word) in content.unicode_word_indices() {
+            let mut chars = word.chars();
+            if let (Some(c), None) = (chars.next(), chars.next())
+                && is_ideograph(c)
+            {
+                if start != ideographs_end {
+                    flush_ideographs(&mut ideographs, &mut words);
+                }
+                ideographs.push(c);
+                ideographs_end = start + word.len();
+                continue;
+            }
+            flush_ideographs(&mut ideographs, &mut words);
+
+            let word = word.to_lowercase().replace('\u{2019}', "'");
+            if word.chars().any(char::is_alphabetic) && !self.stop_words.contains(&word) {
+                words.push(word);
+            } else if !words.is_empty() && breaks.last() != Some(&words.len()) {
+                breaks.push(words.len());
+            }
+        }
+        flush_ideographs(&mut ideographs, &mut words);
+
+        (words, breaks)
+    }
+
+    /// Whether a word from `process_words` is long enough to appear in `favorite_words`.
+    pub(crate) fn is_countable_word(&self, word: &str) -> bool {
+        let length = word.chars().count();
+        if word.chars().any(is_cjk) {
+            length >= MIN_CJK_WORD_LENGTH
+        } else {
+            length >= self.options.min_word_length as usize
+        }
+    }
+}
+
+/// Removes custom emotes, links and mentions, leaving only the text a user typed.
+pub(super) fn strip_markup(content: &str) -> String {
+    replace_markup(content, " ")
+}
+
+fn replace_markup(content: &str, replacement: &str) -> String {
+    let content = CUSTOM_EMOTE_REGEX.replace_all(content, replacement);
+    let content = LINK_REGEX.replace_all(&content, replacement);
+    MENTION_REGEX
+        .replace_all(&content, replacement)
+        .into_owned()
+}
+
+pub(super) fn stop_word_set(options: &ExtractionOptions) -> HashSet<String> {
+    let mut stop_words: HashSet<String> = STOP_WORDS
+        .iter()
+        .filter(|(language, _)| match &options.stop_word_languages {
+            Some(languages) => languages
+                .iter()
+                .any(|l| l.trim().eq_ignore_ascii_case(language)),
+            None => *language == DEFAULT_STOP_WORD_LANGUAGE,
+        })
+        .flat_map(|(_, words)| words.iter().map(|w| w.to_string()))
+        .collect();
+    stop_words.extend(
+        options
+            .custom_stop_words
+            .iter()
+            .map(|w| w.trim().to_lowercase()),
+    );
+    stop_words
+}
+
+fn flush_ideographs(ideographs: &mut Vec<char>, words: &mut Vec<String>) {
+    match ideographs.len() {
+        0 => {}
+        1 => words.push(ideographs[0].to_string()),
+        _ => words.extend(ideographs.windows(2).map(|pair| pair.iter().collect())),
+    }
+    ideographs.clear();
+}
+
+/// Han and Hiragana characters, which Unicode word segmentation splits one by one.
+fn is_ideograph(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{309F}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{2EBEF}')
+}
+
+fn is_cjk(c: char) -> bool {
+    is_ideograph(c)
+        || matches!(c,
+            '\u{30A0}'..='\u{30FF}'
+            | '\u{31F0}'..='\u{31FF}'
+            | '\u{1100}'..='\u{11FF}'
+            | '\u{AC00}'..='\u{D7AF}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_only_english_stop_words_by_default() {
+        let stop_words = stop_word_set(&ExtractionOptions::default());
+        assert!(stop_words.contains("the"));
+        for word in ["come", "man", "war", "also", "con", "son", "met"] {
+            assert!(
+                !stop_words.contains(word),
+                "{word} should not be a stop word"
+            );
+        }
+    }
+
+    #[test]
+    fn merges_requested_languages() {
+        let options = ExtractionOptions {
+            stop_word_languages: Some(vec!["de".to_string(), " IT ".to_string()]),
+            custom_stop_words: vec![" Lol ".to_string()],
+            ..Default::default()
+        };
+        let stop_words = stop_word_set(&options);
+        assert!(stop_words.contains("also"));
+        assert!(stop_words.contains("come"));
+        assert!(stop_words.contains("lol"));
+        assert!(!stop_words.contains("the"));
+    }
+}
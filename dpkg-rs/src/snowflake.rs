@@ -0,0 +1,86 @@
+use chrono::{DateTime, Utc};
+
+/// Discord snowflakes count milliseconds from 2015-01-01T00:00:00Z.
+const DISCORD_EPOCH_MS: u64 = 1_420_070_400_000;
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct Snowflake {
+    pub id: String,
+    /// Creation time in RFC 3339 form, UTC.
+    pub timestamp: String,
+    pub timestamp_ms: i64,
+    pub worker_id: u8,
+    pub process_id: u8,
+    pub increment: u16,
+}
+
+impl Snowflake {
+    pub fn decode(id: u64) -> Option<Self> {
+        let created_at = created_at(id)?;
+        Some(Self {
+            id: id.to_string(),
+            timestamp: created_at.to_rfc3339(),
+            timestamp_ms: created_at.timestamp_millis(),
+            worker_id: ((id >> 17) & 0x1F) as u8,
+            process_id: ((id >> 12) & 0x1F) as u8,
+            increment: (id & 0xFFF) as u16,
+        })
+    }
+}
+
+/// Creation time embedded in a snowflake. `None` for IDs that can't be snowflakes, such as 0.
+pub fn created_at(id: u64) -> Option<DateTime<Utc>> {
+    if id >> 22 == 0 {
+        return None;
+    }
+    DateTime::from_timestamp_millis(((id >> 22) + DISCORD_EPOCH_MS) as i64)
+}
+
+/// Same as [`created_at`] for IDs stored as strings, as in most package files.
+pub fn created_at_str(id: &str) -> Option<DateTime<Utc>> {
+    id.trim().parse().ok().and_then(created_at)
+}
+
+#[uniffi::export]
+fn decode_snowflake(id: String) -> Option<Snowflake> {
+    id.trim().parse().ok().and_then(Snowflake::decode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Example from Discord's API reference.
+    const ID: u64 = 175928847299117063;
+
+    #[test]
+    fn decodes_every_field() {
+        let snowflake = Snowflake::decode(ID).unwrap();
+        assert_eq!(snowflake.id, "175928847299117063");
+        assert_eq!(snowflake.timestamp_ms, 1462015105796);
+        assert_eq!(snowflake.timestamp, "2016-04-30T11:18:25.796+00:00");
+        assert_eq!(snowflake.worker_id, 1);
+        assert_eq!(snowflake.process_id, 0);
+        assert_eq!(snowflake.increment, 7);
+    }
+
+    #[test]
+    fn created_at_rejects_ids_without_a_timestamp() {
+        assert_eq!(created_at(0), None);
+        assert_eq!(created_at((1 << 22) - 1), None);
+        assert_eq!(
+            created_at(1 << 22).map(|t| t.timestamp_millis()),
+            Some(DISCORD_EPOCH_MS as i64 + 1)
+        );
+    }
+
+    #[test]
+    fn created_at_str_parses_trimmed_ids_only() {
+        assert_eq!(
+            created_at_str(" 175928847299117063 ").map(|t| t.timestamp_millis()),
+            Some(1462015105796)
+        );
+        assert_eq!(created_at_str("not an id"), None);
+        assert_eq!(created_at_str("-5"), None);
+    }
+}